@@ -2,24 +2,28 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
+use log::{info, warn};
 
 use crate::input::json_input::{Entry, GlobalFile};
-use crate::input::scheduler::SchedulerOptions;
-use crate::input::toml_input::{self, Contract, Mail};
-use crate::input::{Month, Signature};
+use crate::input::scheduler::UnscheduledPolicy;
+use crate::input::toml_input::{self, Contract, Generator, Mail};
+use crate::input::{DroppedEntry, Month, Signature};
 use crate::latex_string::LatexString;
+use crate::time::Date;
 use crate::utils;
-use crate::working_duration;
 
 #[derive(Debug, Clone)]
 pub struct Config {
     mail: Option<Mail>,
+    contract: Contract,
     global_file: GlobalFile,
     signature: Option<Signature>,
     output: PathBuf,
     preserve_dir: Option<PathBuf>,
     month: Month,
+    unmatched_overrides: Vec<(String, Date)>,
     latex_mk_path: Option<PathBuf>,
+    generator: Generator,
 }
 
 pub struct ConfigBuilder {
@@ -28,6 +32,7 @@ pub struct ConfigBuilder {
     month: toml_input::Month,
     output: Option<PathBuf>,
     preserve_dir: Option<PathBuf>,
+    extension: String,
 }
 
 impl ConfigBuilder {
@@ -41,6 +46,7 @@ impl ConfigBuilder {
         Ok(Self {
             output: None,
             preserve_dir: None,
+            extension: "pdf".to_string(),
             global,
             month,
             contract,
@@ -57,9 +63,16 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the extension of the output file, if no explicit filename has been specified.
+    pub fn extension(&mut self, extension: impl Into<String>) -> &mut Self {
+        self.extension = extension.into();
+        self
+    }
+
     #[must_use]
     pub fn build(self) -> anyhow::Result<Config> {
-        let default_file_name = PathBuf::from(self.global.resolve_output(&self.month));
+        let default_file_name =
+            PathBuf::from(self.global.resolve_output(&self.month, &self.extension));
 
         let output = {
             if let Some(output) = self.output {
@@ -73,6 +86,8 @@ impl ConfigBuilder {
             }
         };
 
+        let holidays = self.global.holidays();
+
         let dynamic_entries: Vec<_> = self
             .month
             .dynamic_entries()
@@ -82,11 +97,24 @@ impl ConfigBuilder {
             .chain(self.global.dynamic_repeating_in_month(
                 self.month.general().year(),
                 self.month.general().month(),
-                |date| date.is_workday(),
+                |date| date.is_workday(&holidays),
                 self.contract.department(),
             ))
             .collect();
 
+        if self.month.general().working_hours().is_some() {
+            warn!("`working_hours` in the `[general]` section is deprecated, use the `[scheduler]` section instead");
+        }
+
+        let mut options = self
+            .contract
+            .scheduler()
+            .merge(&self.month.scheduler())
+            .to_options()
+            .context("invalid scheduler settings")?;
+        options.unscheduled = self.month.general().unscheduled();
+        options.holidays = holidays.clone();
+
         let mut month = Month::new(
             self.month.general().month(),
             self.month.general().year(),
@@ -95,11 +123,7 @@ impl ConfigBuilder {
             dynamic_entries,
             Some(self.contract.expected_working_duration()),
             self.month.absences().collect::<Vec<_>>(),
-            SchedulerOptions {
-                daily_limit: working_duration!(06:00),
-                strategy: self.month.general().strategy(),
-                ..Default::default()
-            },
+            options,
         );
 
         for entry in self
@@ -107,7 +131,7 @@ impl ConfigBuilder {
             .repeating_in_month(
                 self.month.general().year(),
                 self.month.general().month(),
-                |date| date.is_workday(),
+                |date| date.is_workday(&holidays),
                 self.contract.department(),
             )
             .map(Entry::from)
@@ -115,10 +139,55 @@ impl ConfigBuilder {
             month.add_entry_if_possible(entry);
         }
 
-        if let Some(holiday) = self.month.holiday() {
+        let unmatched_overrides = self
+            .global
+            .unmatched_overrides(
+                self.month.general().year(),
+                self.month.general().month(),
+                |date| date.is_workday(&holidays),
+                self.contract.department(),
+            )
+            .map(|(action, date)| (action.to_string(), date))
+            .collect();
+
+        for holiday in self.month.holidays() {
             month.schedule_holiday(holiday)?;
         }
 
+        for sick in self.month.sick_leave() {
+            month.schedule_sick(sick)?;
+        }
+
+        // make sure that the dynamic entries can be placed without overlaps
+        month.schedule_dynamic_entries();
+
+        let unscheduled = month.unscheduled_work();
+        if !unscheduled.is_empty() {
+            let missing = unscheduled
+                .iter()
+                .map(|work| format!("`{}` ({})", work.action(), work.duration()))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            match self.month.general().unscheduled() {
+                UnscheduledPolicy::Warn => {
+                    warn!("failed to schedule the dynamic work of {}", missing);
+                }
+                UnscheduledPolicy::Error => {
+                    return Err(anyhow::anyhow!(
+                        "failed to schedule the dynamic work of {}",
+                        missing
+                    ));
+                }
+                UnscheduledPolicy::Transfer => {
+                    info!(
+                        "balancing the missing dynamic work of {} through the transfer",
+                        missing
+                    );
+                }
+            }
+        }
+
         let mut preserve_dir = self.preserve_dir;
 
         if let Some(dir) = self.global.preserve_dir() {
@@ -127,7 +196,9 @@ impl ConfigBuilder {
 
         Ok(Config {
             month,
+            unmatched_overrides,
             mail: self.global.mail().cloned(),
+            contract: self.contract.clone(),
             global_file: GlobalFile::from((
                 self.global.about().clone(),
                 self.contract.department().to_string(),
@@ -147,8 +218,9 @@ impl ConfigBuilder {
                 }
             },
             output,
-            preserve_dir: preserve_dir,
+            preserve_dir,
             latex_mk_path: self.global.latex_mk_path().map(|v| v.to_path_buf()),
+            generator: self.global.generator(),
         })
     }
 }
@@ -177,10 +249,14 @@ impl Config {
         &self.output
     }
 
-    fn global_file(&self) -> &GlobalFile {
+    pub(crate) fn global_file(&self) -> &GlobalFile {
         &self.global_file
     }
 
+    pub fn contract(&self) -> &Contract {
+        &self.contract
+    }
+
     pub fn signature(&self) -> Option<&Signature> {
         self.signature.as_ref()
     }
@@ -197,10 +273,25 @@ impl Config {
         &self.month
     }
 
+    /// The occurrences of repeating events that could not be added to the month.
+    pub fn dropped_entries(&self) -> &[DroppedEntry] {
+        self.month.dropped_entries()
+    }
+
+    /// The overrides of repeating events, that do not match an occurrence in
+    /// the month, together with the action of the event.
+    pub fn unmatched_overrides(&self) -> &[(String, Date)] {
+        &self.unmatched_overrides
+    }
+
     pub fn latex_mk_path(&self) -> Option<&Path> {
         self.latex_mk_path.as_deref()
     }
 
+    pub fn generator(&self) -> Generator {
+        self.generator
+    }
+
     pub fn mail(&self) -> Option<&Mail> {
         self.mail.as_ref()
     }
@@ -210,8 +301,10 @@ impl Config {
         Ok(())
     }
 
+    /// Writes the month file for the TimeSheetGenerator jar.
     pub fn write_month_json(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
-        utils::write(path, self.to_month_json()?)?;
+        let month_file = self.month().to_month_file().for_jar();
+        utils::write(path, serde_json::to_string_pretty(&month_file)?)?;
         Ok(())
     }
 
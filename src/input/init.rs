@@ -0,0 +1,245 @@
+use core::fmt;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use lettre::Address;
+
+use crate::input::toml_input;
+use crate::input::WorkingArea;
+use crate::time::{Date, WorkingDuration};
+
+/// Settings for sending the time sheet through an email.
+#[derive(Debug, Clone, PartialEq)]
+struct MailSettings {
+    name: String,
+    email: String,
+    url: String,
+    username: String,
+    use_starttls: bool,
+}
+
+/// The answers from which a global file is made, which can be written as a
+/// commented toml file.
+#[derive(Debug, Clone)]
+pub struct GlobalScaffold {
+    name: String,
+    staff_id: usize,
+    signature: Option<PathBuf>,
+    department: String,
+    working_time: WorkingDuration,
+    area: WorkingArea,
+    wage: Option<f32>,
+    start_date: Date,
+    end_date: Date,
+    mail: Option<MailSettings>,
+}
+
+/// Asks the question until the answer can be parsed, the error is shown to the
+/// user after each invalid answer.
+fn ask<T>(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    parse: impl Fn(&str) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    loop {
+        write!(output, "{}: ", question)?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Err(anyhow::anyhow!("no answer for \"{}\"", question));
+        }
+
+        match parse(line.trim()) {
+            Ok(value) => return Ok(value),
+            Err(error) => writeln!(output, "{}", error)?,
+        }
+    }
+}
+
+fn non_empty(answer: &str) -> anyhow::Result<String> {
+    if answer.is_empty() {
+        return Err(anyhow::anyhow!("the answer must not be empty"));
+    }
+
+    Ok(answer.to_string())
+}
+
+fn yes_or_no(answer: &str) -> anyhow::Result<bool> {
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "" | "n" | "no" => Ok(false),
+        _ => Err(anyhow::anyhow!("please answer with \"y\" or \"n\"")),
+    }
+}
+
+/// Returns the string as a toml string with quotes.
+fn quoted(string: &str) -> toml::Value {
+    toml::Value::String(string.to_string())
+}
+
+impl GlobalScaffold {
+    /// Asks for everything that is needed for the global file.
+    ///
+    /// The answers are checked with the same parsers that are used when
+    /// loading the file.
+    pub fn ask(input: &mut impl BufRead, output: &mut impl Write) -> anyhow::Result<Self> {
+        let name = ask(input, output, "Name (first and last name)", non_empty)?;
+        let staff_id = ask(input, output, "Staff id", |answer| {
+            Ok(answer.parse::<usize>()?)
+        })?;
+        let signature = ask(
+            input,
+            output,
+            "Path to an image of your signature (optional)",
+            |answer| Ok((!answer.is_empty()).then(|| PathBuf::from(answer))),
+        )?;
+
+        let department = ask(input, output, "Department (like \"IANA\")", non_empty)?;
+        let working_time = ask(
+            input,
+            output,
+            "Working time per month (like \"40:00\")",
+            |answer| answer.parse::<WorkingDuration>(),
+        )?;
+        let area = ask(
+            input,
+            output,
+            "Area (\"ub\" for universitätsbereich, \"gf\" for großforschungsbereich)",
+            |answer| answer.parse::<WorkingArea>(),
+        )?;
+        let wage = ask(input, output, "Wage per hour (optional)", |answer| {
+            Ok((!answer.is_empty())
+                .then(|| answer.parse::<f32>())
+                .transpose()?)
+        })?;
+        let start_date = ask(
+            input,
+            output,
+            "Start of the contract (like \"2022-01-01\")",
+            |answer| Ok(answer.parse::<Date>()?),
+        )?;
+        let end_date = ask(input, output, "End of the contract", |answer| {
+            let date = answer.parse::<Date>()?;
+            if date < start_date {
+                return Err(anyhow::anyhow!(
+                    "the contract must end after {}",
+                    start_date
+                ));
+            }
+
+            Ok(date)
+        })?;
+
+        let mail = if ask(
+            input,
+            output,
+            "Do you want to send the time sheet through an email? [y/N]",
+            yes_or_no,
+        )? {
+            Some(MailSettings {
+                name: name.clone(),
+                email: ask(input, output, "Your email address", |answer| {
+                    answer.parse::<Address>()?;
+                    Ok(answer.to_string())
+                })?,
+                url: ask(
+                    input,
+                    output,
+                    "SMTP server (like \"smtp.kit.edu\")",
+                    non_empty,
+                )?,
+                username: ask(input, output, "SMTP username", non_empty)?,
+                use_starttls: ask(input, output, "Use STARTTLS? [y/N]", yes_or_no)?,
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            name,
+            staff_id,
+            signature,
+            department,
+            working_time,
+            area,
+            wage,
+            start_date,
+            end_date,
+            mail,
+        })
+    }
+
+    /// Parses the scaffold like a global file.
+    pub fn to_global(&self) -> anyhow::Result<toml_input::Global> {
+        Ok(toml::from_str(&self.to_string())?)
+    }
+}
+
+impl fmt::Display for GlobalScaffold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Data that does not change between the months.")?;
+        writeln!(f, "[about]")?;
+        writeln!(f, "name = {}", quoted(&self.name))?;
+        writeln!(f, "staff_id = {}", self.staff_id)?;
+        writeln!(f)?;
+
+        writeln!(f, "# An image of the signature, which is put on the sheet.")?;
+        if let Some(path) = &self.signature {
+            writeln!(f, "[about.signature]")?;
+            writeln!(f, "path = {}", quoted(&path.display().to_string()))?;
+            writeln!(f, "# the width of the signature in cm (default is 3.8)")?;
+            writeln!(f, "# width = 3.8")?;
+        } else {
+            writeln!(f, "# [about.signature]")?;
+            writeln!(f, "# path = \"signature.png\"")?;
+        }
+        writeln!(f)?;
+
+        writeln!(
+            f,
+            "# The contract, the department is used in the month files."
+        )?;
+        writeln!(f, "# One can add more contracts in the same way.")?;
+        writeln!(f, "[contract.{}]", quoted(&self.department))?;
+        writeln!(f, "# how much one should work in a month")?;
+        writeln!(f, "working_time = \"{}\"", self.working_time)?;
+        writeln!(f, "# gf = großforschungsbereich")?;
+        writeln!(f, "# ub = universitätsbereich")?;
+        writeln!(f, "area = \"{}\"", self.area)?;
+        if let Some(wage) = self.wage {
+            writeln!(f, "# how much one makes per hour")?;
+            writeln!(f, "wage = {:.2}", wage)?;
+        }
+        writeln!(f, "start_date = {}", self.start_date)?;
+        writeln!(f, "end_date = {}", self.end_date)?;
+
+        if let Some(mail) = &self.mail {
+            writeln!(f)?;
+            writeln!(f, "# Used by the `send` command.")?;
+            writeln!(f, "[mail.from]")?;
+            writeln!(f, "name = {}", quoted(&mail.name))?;
+            writeln!(f, "email = {}", quoted(&mail.email))?;
+            writeln!(f)?;
+            writeln!(f, "[mail.smtp]")?;
+            writeln!(f, "url = {}", quoted(&mail.url))?;
+            writeln!(f, "username = {}", quoted(&mail.username))?;
+            writeln!(
+                f,
+                "# the password is not asked for, so it is not shown in the terminal"
+            )?;
+            writeln!(f, "# password = \"your password\"")?;
+            writeln!(f, "use_starttls = {}", mail.use_starttls)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "# Events that repeat, like a weekly tutorium:")?;
+        writeln!(f, "# [repeating.\"Tutorium\"]")?;
+        writeln!(f, "# start = \"14:00\"")?;
+        writeln!(f, "# end = \"16:00\"")?;
+        writeln!(f, "# repeats_on = [\"Tuesday\"]")?;
+        writeln!(f, "# repeats_every = \"week\"")?;
+        writeln!(f, "# department = {}", quoted(&self.department))
+    }
+}
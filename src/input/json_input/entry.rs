@@ -22,6 +22,8 @@ pub struct Entry {
     pause: Option<WorkingDuration>,
     #[serde(skip_serializing_if = "is_false", default)]
     vacation: bool,
+    #[serde(skip_serializing_if = "is_false", default)]
+    sick: bool,
 }
 
 impl Entry {
@@ -39,6 +41,7 @@ impl Entry {
             end,
             pause,
             vacation: false,
+            sick: false,
         };
 
         // automatically add pauses if they are missing:
@@ -67,9 +70,32 @@ impl Entry {
             end,
             pause: None,
             vacation: true,
+            sick: false,
         }
     }
 
+    /// Makes an entry for a day on which one has been sick, the time
+    /// is credited like it has been worked.
+    pub fn new_sick(action: impl ToString, day: usize, start: TimeStamp, end: TimeStamp) -> Self {
+        Self {
+            action: action.to_string(),
+            day,
+            start,
+            end,
+            pause: None,
+            vacation: false,
+            sick: true,
+        }
+    }
+
+    /// Turns a sick entry into a normal entry, because the TimeSheetGenerator
+    /// jar does not know the `sick` field.
+    #[must_use]
+    pub(crate) fn without_sick(mut self) -> Self {
+        self.sick = false;
+        self
+    }
+
     #[must_use]
     fn with_pause(mut self, pause: WorkingDuration) -> Self {
         let duration = self.work_duration();
@@ -83,6 +109,8 @@ impl From<&toml_input::Entry> for Entry {
     fn from(entry: &toml_input::Entry) -> Self {
         if entry.is_vacation() {
             Self::new_vacation(entry.action(), entry.day(), entry.start(), entry.end())
+        } else if entry.is_sick() {
+            Self::new_sick(entry.action(), entry.day(), entry.start(), entry.end())
         } else {
             Self::new(
                 entry.action(),
@@ -218,6 +246,10 @@ impl Entry {
         self.vacation
     }
 
+    pub const fn is_sick(&self) -> bool {
+        self.sick
+    }
+
     pub fn action(&self) -> &str {
         &self.action
     }
@@ -51,6 +51,31 @@ impl From<(toml_input::About, String, toml_input::Contract)> for GlobalFile {
 }
 
 impl GlobalFile {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn staff_id(&self) -> usize {
+        self.staff_id
+    }
+
+    #[must_use]
+    pub fn department(&self) -> &str {
+        &self.department
+    }
+
+    #[must_use]
+    pub fn wage(&self) -> f32 {
+        self.wage
+    }
+
+    #[must_use]
+    pub fn working_area(&self) -> WorkingArea {
+        self.working_area
+    }
+
     #[must_use]
     pub fn bg_content(&self) -> Option<&LatexString> {
         self.bg_content.as_ref()
@@ -60,4 +60,12 @@ impl MonthFile {
     pub fn transfer(&self) -> Transfer {
         Transfer::new(self.pred_transfer, self.succ_transfer)
     }
+
+    /// Returns the month file in the format of the TimeSheetGenerator jar,
+    /// which rejects the fields it does not know, like `sick`.
+    #[must_use]
+    pub fn for_jar(mut self) -> Self {
+        self.entries = self.entries.into_iter().map(Entry::without_sick).collect();
+        self
+    }
 }
@@ -1,7 +1,12 @@
 mod config;
+mod init;
 mod month;
+mod new_month;
+mod overtime;
 mod sign;
 mod signature;
+mod vacation;
+mod validator;
 mod working_area;
 
 pub mod json_input;
@@ -10,10 +15,15 @@ pub mod strategy;
 pub mod toml_input;
 
 pub use config::*;
+pub use init::*;
 pub use month::*;
+pub use new_month::*;
+pub use overtime::*;
 pub use scheduler::Scheduler;
 pub use sign::*;
 pub use signature::*;
 pub use strategy::Task;
 pub use toml_input::Transfer;
+pub use vacation::*;
+pub use validator::*;
 pub use working_area::*;
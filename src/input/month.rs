@@ -1,13 +1,139 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+use derive_more::Display;
 use log::debug;
 use serde::ser;
 use serde::Serialize;
 
 use crate::input::json_input::{Entry, MonthFile};
-use crate::input::scheduler::SchedulerOptions;
-use crate::input::toml_input::{Absence, DynamicEntry, Holiday, Transfer};
+use crate::input::scheduler::{ScheduledTime, SchedulerOptions, SlotAllocator, UnscheduledPolicy};
+use crate::input::toml_input::{Absence, DynamicEntry, Holiday, Sick, Transfer};
 use crate::input::Task;
+use crate::time::holiday::HolidayCalendar;
 use crate::time::{self, Date, TimeSpan, TimeStamp, WorkingDuration, Year};
-use crate::{time_stamp, working_duration};
+use crate::{min, time_stamp, working_duration};
+
+/// Where an entry of the month comes from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+pub enum EntrySource {
+    /// Explicitly specified in the month file.
+    #[display("fixed")]
+    Fixed,
+    /// Added through a repeating event in the global file.
+    #[display("repeating")]
+    Repeating,
+    /// Distributed from a dynamic entry.
+    #[display("dynamic")]
+    Dynamic,
+    #[display("vacation")]
+    Vacation,
+    #[display("sick")]
+    Sick,
+}
+
+impl EntrySource {
+    #[must_use]
+    fn of_fixed(entry: &Entry) -> Self {
+        if entry.is_vacation() {
+            Self::Vacation
+        } else if entry.is_sick() {
+            Self::Sick
+        } else {
+            Self::Fixed
+        }
+    }
+}
+
+/// Dynamic work of a single action, that could not be placed in the month.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnscheduledWork {
+    action: String,
+    duration: WorkingDuration,
+}
+
+impl UnscheduledWork {
+    #[must_use]
+    pub fn new(action: impl Into<String>, duration: WorkingDuration) -> Self {
+        Self {
+            action: action.into(),
+            duration,
+        }
+    }
+
+    #[must_use]
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    #[must_use]
+    pub const fn duration(&self) -> WorkingDuration {
+        self.duration
+    }
+
+    /// Adds the duration to the work of the action, so there is at most one
+    /// entry for every action.
+    fn add_to(unscheduled: &mut Vec<Self>, action: &str, duration: WorkingDuration) {
+        if duration == WorkingDuration::default() {
+            return;
+        }
+
+        if let Some(work) = unscheduled.iter_mut().find(|work| work.action() == action) {
+            work.duration += duration;
+        } else {
+            unscheduled.push(Self::new(action, duration));
+        }
+    }
+}
+
+/// Why an occurrence of a repeating event could not be added to the month.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum DropReason {
+    /// The occurrence overlaps with an absence.
+    #[display("it overlaps with an absence")]
+    AbsenceOverlap,
+    /// The occurrence overlaps with another entry of the month.
+    #[display("it overlaps with `{}`", action)]
+    EntryOverlap { action: String },
+    /// The occurrence would exceed the maximum working time of the day.
+    #[display("it would exceed the maximum working time of {}", maximum)]
+    DailyLimitExceeded { maximum: WorkingDuration },
+    /// The occurrence is on a day of vacation or sick leave.
+    #[display("the day is blocked by vacation or sick leave")]
+    BlockedDay,
+}
+
+/// An occurrence of a repeating event, that has not been added to the month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DroppedEntry {
+    date: Date,
+    entry: Entry,
+    reason: DropReason,
+}
+
+impl DroppedEntry {
+    #[must_use]
+    pub const fn date(&self) -> Date {
+        self.date
+    }
+
+    #[must_use]
+    pub const fn entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    #[must_use]
+    pub const fn reason(&self) -> &DropReason {
+        &self.reason
+    }
+}
+
+/// The entries of the month after the dynamic entries have been placed.
+#[derive(Debug, Clone)]
+struct Schedule {
+    entries: Vec<(EntrySource, Entry)>,
+    unscheduled: Vec<UnscheduledWork>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Month {
@@ -16,13 +142,17 @@ pub struct Month {
     dynamic_entries: Vec<DynamicEntry>,
     expected_working_duration: Option<WorkingDuration>,
     transfer: Transfer,
-    entries: Vec<Entry>,
+    entries: Vec<(EntrySource, Entry)>,
     absence: Vec<(Date, Absence)>,
+    blocked_days: Vec<Date>,
+    dropped: Vec<DroppedEntry>,
     options: SchedulerOptions,
+    /// Computed once by [`Month::schedule_dynamic_entries`] and cleared when
+    /// the month changes.
+    schedule: Option<Schedule>,
 }
 
 impl Month {
-    const MAXIMUM_WORK_DURATION: WorkingDuration = working_duration!(08:00);
     const DEFAULT_START: TimeStamp = time_stamp!(10:00);
 
     #[must_use]
@@ -40,29 +170,80 @@ impl Month {
             month,
             year,
             transfer,
-            entries,
+            entries: entries
+                .into_iter()
+                .map(|entry| (EntrySource::of_fixed(&entry), entry))
+                .collect(),
             dynamic_entries,
             expected_working_duration,
             absence,
+            blocked_days: Vec::new(),
+            dropped: Vec::new(),
             options,
+            schedule: None,
         }
     }
 
+    /// Adds the entry of a repeating event, if it fits on its day.
+    ///
+    /// Otherwise the entry is remembered together with the reason why it has
+    /// been dropped, see [`Month::dropped_entries`].
     pub fn add_entry_if_possible(&mut self, entry: Entry) {
+        self.schedule = None;
+
         let span = entry.time_span();
         let entry_date = Date::new(self.year, self.month, entry.day()).unwrap();
         // TODO: is span.duration() right? This would include pauses
         let scheduled = self.schedule(
             Task::new_duration(span.duration())
                 .with_start(span.start())
-                .with_suggested_date(entry_date),
+                .with_suggested_date(entry_date)
+                .with_can_be_split(false),
         );
 
         if let Some((date, span)) = scheduled.get(0) {
             if *date == entry_date && *span == entry.time_span() {
-                self.entries.push(entry);
+                self.entries.push((EntrySource::Repeating, entry));
+                return;
             }
         }
+
+        let reason = self.drop_reason(entry_date, span);
+        debug!("dropped `{}` on {}: {}", entry.action(), entry_date, reason);
+        self.dropped.push(DroppedEntry {
+            date: entry_date,
+            entry,
+            reason,
+        });
+    }
+
+    /// Finds out why the time span can not be placed on that date.
+    fn drop_reason(&self, date: Date, span: TimeSpan) -> DropReason {
+        if self
+            .absences_on_day(date)
+            .any(|absence| absence.time_span().overlaps_with(span))
+        {
+            return DropReason::AbsenceOverlap;
+        }
+
+        if let Some(entry) = self
+            .entries_on_day(date)
+            .find(|entry| entry.time_span().overlaps_with(span))
+        {
+            return DropReason::EntryOverlap {
+                action: entry.action().to_string(),
+            };
+        }
+
+        DropReason::DailyLimitExceeded {
+            maximum: self.maximum_work_duration(),
+        }
+    }
+
+    /// The occurrences of repeating events that could not be added to the month.
+    #[must_use]
+    pub fn dropped_entries(&self) -> &[DroppedEntry] {
+        &self.dropped
     }
 
     /// Returns the amount of time that is remaining to be worked in this month.
@@ -71,8 +252,7 @@ impl Month {
     /// duration.
     pub fn remaining_time(&self) -> Transfer {
         let fixed_work_duration = self
-            .entries
-            .iter()
+            .entries()
             .map(|e| e.work_duration())
             .sum::<WorkingDuration>();
 
@@ -81,52 +261,192 @@ impl Month {
 
     /// Finds a free spot where the task can be placed.
     /// In case the task must be split up, multiple spots will be returned.
+    ///
+    /// If there is no free spot, an empty list is returned.
     fn schedule(&self, task: Task) -> Vec<(Date, TimeSpan)> {
-        let mut result = Vec::new();
-
         let start = task.suggested_start().unwrap_or(Self::DEFAULT_START);
+        let span = TimeSpan::new(start, start + task.duration());
         let mut iter = self.days_with_time_for(task.duration(), Some(start));
 
-        let first = iter.next().expect("No free spot found for task!");
-
         if let Some(date) = task.suggested_date() {
-            if date == first || iter.find(|d| *d == date).is_some() {
-                result.push((date, TimeSpan::new(start, start + task.duration())));
-                return result;
+            if iter.any(|d| d == date) {
+                return vec![(date, span)];
             }
+        } else if let Some(first) = iter.next() {
+            return vec![(first, span)];
+        }
+
+        if task.can_be_split() {
+            self.schedule_split(task, start)
         } else {
-            result.push((first, TimeSpan::new(start, start + task.duration())));
+            Vec::new()
         }
+    }
 
-        // TODO: should one implement splitting up the task?
+    /// Splits the task into multiple parts that are placed around the existing entries,
+    /// starting on the suggested date and continuing on the following workdays.
+    ///
+    /// If the task does not fit into the remaining month, an empty list is returned.
+    fn schedule_split(&self, task: Task, start: TimeStamp) -> Vec<(Date, TimeSpan)> {
+        let mut result = Vec::new();
+        let mut remaining = task.duration();
 
-        result
+        let days = self
+            .year()
+            .days_in(self.month())
+            .filter(|date| task.suggested_date().is_none_or(|first| *date >= first))
+            .filter(|date| date.is_workday(&self.options.holidays) && task.applies_on(*date));
+
+        for date in days {
+            let mut allocator = self.slot_allocator_on(date);
+            let mut available = self
+                .maximum_work_duration()
+                .saturating_sub(self.working_time_on_day(date));
+
+            while remaining > WorkingDuration::default() && available > WorkingDuration::default() {
+                let Some(span) = allocator.allocate_up_to(min!(remaining, available), start) else {
+                    break;
+                };
+
+                remaining -= span.duration();
+                available -= span.duration();
+                result.push((date, span));
+            }
+
+            if remaining == WorkingDuration::default() {
+                return result;
+            }
+        }
+
+        Vec::new()
+    }
+
+    /// Returns an allocator for the free time slots on that day.
+    #[must_use]
+    fn slot_allocator_on(&self, date: Date) -> SlotAllocator {
+        let mut allocator = SlotAllocator::new(self.options.working_hours);
+
+        for entry in self.entries_on_day(date) {
+            allocator.occupy(entry.time_span());
+        }
+
+        for absence in self.absences_on_day(date) {
+            allocator.occupy(absence.time_span());
+        }
+
+        if let Some(availability) = &self.options.availability {
+            for span in availability.unavailable_on(date.week_day()) {
+                allocator.occupy(span);
+            }
+        }
+
+        allocator
+    }
+
+    /// Returns the longest time span of the day, in which a dynamic entry could
+    /// be placed without overlapping other entries, absences or the unavailable
+    /// times.
+    #[must_use]
+    pub fn longest_free_span_on(&self, date: Date) -> WorkingDuration {
+        self.slot_allocator_on(date).longest_free()
     }
 
     pub fn schedule_holiday(&mut self, holiday: &Holiday) -> anyhow::Result<()> {
-        let entry = holiday.to_entry(
+        self.schedule = None;
+
+        if holiday.is_block() {
+            self.block_days(holiday.day()..=holiday.last_day());
+        }
+
+        let entry = holiday.to_entries(
             self.year,
             self.month,
             self.real_expected_working_duration(),
+            |date| date.is_workday(&self.options.holidays),
             |task| self.schedule(task),
-        );
+        )?;
 
-        // when a fixed entry is scheduled on the same day a holiday is,
-        // the holiday will not be present in the final output.
+        // when there is not enough free time on the day of the holiday and the
+        // following workdays, the holiday will not be present in the final output.
 
         // To prevent this, an error is raised.
         if entry.is_empty() {
             return Err(anyhow::anyhow!(
-                "Failed to schedule holiday on the day {}. Please make sure that there are no other entries on that day.",
+                "Failed to schedule holiday on the day {}. Please make sure that there is enough free time on that day or the following workdays.",
                 holiday.day()
             ));
         }
 
-        self.entries.extend(entry);
+        self.entries
+            .extend(entry.into_iter().map(|e| (EntrySource::Vacation, e)));
+
+        Ok(())
+    }
+
+    pub fn schedule_sick(&mut self, sick: &Sick) -> anyhow::Result<()> {
+        self.schedule = None;
+
+        self.block_days(sick.day()..=sick.last_day());
+
+        let day_duration = Holiday::day_duration(self.real_expected_working_duration());
+        let entries = sick.to_entries(
+            self.year,
+            self.month,
+            |date| {
+                if !date.is_workday(&self.options.holidays) {
+                    return Vec::new();
+                }
+
+                // one is credited the time one would have worked on that day
+                match &self.options.availability {
+                    Some(availability) => availability.windows_on(date.week_day()).to_vec(),
+                    None => vec![TimeSpan::new(
+                        Self::DEFAULT_START,
+                        Self::DEFAULT_START + day_duration,
+                    )],
+                }
+            },
+            |task| self.schedule(task),
+        )?;
+
+        self.entries
+            .extend(entries.into_iter().map(|e| (EntrySource::Sick, e)));
 
         Ok(())
     }
 
+    /// Prevents that anything else is scheduled on the days, by removing the
+    /// repeating entries on them and excluding them from the dynamic entries.
+    fn block_days(&mut self, days: RangeInclusive<usize>) {
+        self.schedule = None;
+
+        let (blocked, entries) =
+            self.entries
+                .drain(..)
+                .partition::<Vec<_>, _>(|(source, entry)| {
+                    *source == EntrySource::Repeating && days.contains(&entry.day())
+                });
+        self.entries = entries;
+
+        for (_, entry) in blocked {
+            self.dropped.push(DroppedEntry {
+                date: Date::new(self.year, self.month, entry.day()).unwrap(),
+                entry,
+                reason: DropReason::BlockedDay,
+            });
+        }
+
+        self.blocked_days
+            .extend(days.filter_map(|day| Date::new(self.year, self.month, day).ok()));
+    }
+
+    /// Whether the date is part of a vacation block or sick leave, on which
+    /// no other work should be scheduled.
+    #[must_use]
+    pub fn is_blocked_day(&self, date: Date) -> bool {
+        self.blocked_days.contains(&date)
+    }
+
     /// Returns the amount of time that the user should have worked in this month.
     ///
     /// For example if the user has to work 40 hours a month, then there will be
@@ -162,8 +482,7 @@ impl Month {
     #[must_use]
     pub fn total_working_time(&self) -> WorkingDuration {
         // add the time from the previous/next month
-        self.entries
-            .iter()
+        self.entries()
             .map(|e| e.work_duration())
             .sum::<WorkingDuration>()
             + self.transfer.previous()
@@ -187,7 +506,13 @@ impl Month {
 
     #[must_use]
     pub const fn maximum_work_duration(&self) -> WorkingDuration {
-        Self::MAXIMUM_WORK_DURATION
+        self.options.maximum_work_per_day
+    }
+
+    /// The public holidays on which no work should be done.
+    #[must_use]
+    pub const fn holidays(&self) -> &HolidayCalendar {
+        &self.options.holidays
     }
 
     #[must_use]
@@ -226,8 +551,7 @@ impl Month {
 
     /// Returns an iterator over all entries that are on the given day.
     fn entries_on_day(&self, date: Date) -> impl Iterator<Item = &Entry> + '_ {
-        self.entries
-            .iter()
+        self.entries()
             .filter(move |entry| entry.day() == date.day())
     }
 
@@ -246,13 +570,20 @@ impl Month {
     /// Returns the transfer time for the month.
     /// (how much time is transfered to the next month/from the previous month)
     #[must_use]
-    const fn transfer(&self) -> Transfer {
+    pub const fn transfer(&self) -> Transfer {
         self.transfer
     }
 
-    fn to_month_file(&self) -> MonthFile {
-        let mut entries = self.entries.clone();
+    /// Returns all entries that are not dynamic.
+    fn entries(&self) -> impl Iterator<Item = &Entry> + '_ {
+        self.entries.iter().map(|(_, entry)| entry)
+    }
 
+    /// Distributes the dynamic entries over the month and returns the scheduled
+    /// times together with the work that could not be placed.
+    fn distribute_dynamic_entries(
+        &self,
+    ) -> (Vec<(&DynamicEntry, ScheduledTime)>, Vec<UnscheduledWork>) {
         let mut mapping = Vec::with_capacity(self.dynamic_entries.len());
         let mut durations = Vec::with_capacity(mapping.capacity());
 
@@ -265,24 +596,171 @@ impl Month {
         let distribution = DynamicEntry::distribute(durations.into_iter(), self, &self.options);
 
         debug!("transfer: {:?}", distribution.transfer_time());
-        // TODO: what to do with the transfer_tasks and transfer?
 
-        for (id, time) in distribution.schedule() {
-            let dynamic_entry = mapping[id];
+        let mut unscheduled: Vec<UnscheduledWork> = Vec::new();
+        for (id, task) in distribution.remaining() {
+            UnscheduledWork::add_to(&mut unscheduled, mapping[*id].action(), task.duration());
+        }
+
+        let scheduled = distribution
+            .schedule()
+            .into_iter()
+            .map(|(id, time)| (mapping[id], time))
+            .collect();
+
+        (scheduled, unscheduled)
+    }
+
+    /// Places the scheduled dynamic entries in free time slots of their day,
+    /// so they do not overlap with other entries or absences.
+    ///
+    /// If there is no slot that is long enough, the entry is split into the free
+    /// slots of the day and what does not fit is added to the `unscheduled` work.
+    fn place_dynamic_entries(
+        &self,
+        scheduled: Vec<(&DynamicEntry, ScheduledTime)>,
+        unscheduled: &mut Vec<UnscheduledWork>,
+    ) -> Vec<(EntrySource, Entry)> {
+        let mut allocators: HashMap<Date, SlotAllocator> = HashMap::new();
+        let mut result = Vec::with_capacity(scheduled.len());
+
+        for (dynamic_entry, time) in scheduled {
+            let allocator = allocators
+                .entry(time.date())
+                .or_insert_with(|| self.slot_allocator_on(time.date()));
+            let preferred = dynamic_entry.start().unwrap_or(Self::DEFAULT_START);
+
+            if let Some(span) = allocator.allocate(dynamic_entry.length(&time), preferred) {
+                result.push((
+                    EntrySource::Dynamic,
+                    dynamic_entry.to_entry(span.start(), time),
+                ));
+                continue;
+            }
+
+            let mut remaining = time.duration();
+            while remaining > WorkingDuration::default() {
+                let Some(span) = allocator.allocate_up_to(remaining, preferred) else {
+                    break;
+                };
+
+                let entry = Self::part_entry(dynamic_entry.action(), time.date(), span);
+                remaining = remaining.saturating_sub(entry.work_duration());
+                result.push((EntrySource::Dynamic, entry));
+            }
+
+            if remaining > WorkingDuration::default() {
+                debug!(
+                    "no free time slot for {} of `{}` on {}",
+                    remaining,
+                    dynamic_entry.action(),
+                    time.date()
+                );
+            }
+
+            UnscheduledWork::add_to(unscheduled, dynamic_entry.action(), remaining);
+        }
+
+        result
+    }
 
-            entries.push(dynamic_entry.to_entry(Self::DEFAULT_START, time));
+    /// Makes an entry for a part of a split dynamic entry, that fits into the span
+    /// together with the pause, which is added to long entries.
+    #[must_use]
+    fn part_entry(action: &str, date: Date, span: TimeSpan) -> Entry {
+        let entry = Entry::new(action, date.day(), span.start(), span.end(), None);
+        if entry.time_span().end() <= span.end() {
+            return entry;
         }
 
+        let pause = entry.break_duration();
+        Entry::new(
+            action,
+            date.day(),
+            span.start(),
+            span.start() + (span.duration() - pause),
+            None,
+        )
+    }
+
+    /// Distributes and places the dynamic entries.
+    fn compute_schedule(&self) -> Schedule {
+        let (scheduled, mut unscheduled) = self.distribute_dynamic_entries();
+
+        let mut entries = self.entries.clone();
+        entries.extend(self.place_dynamic_entries(scheduled, &mut unscheduled));
+
         // sort the entries in the json file, so that no problems occur with the java tool
-        entries.sort();
+        entries.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        Schedule {
+            entries,
+            unscheduled,
+        }
+    }
+
+    /// Schedules the dynamic entries once, so they do not have to be distributed
+    /// again every time the entries of the month are needed.
+    pub fn schedule_dynamic_entries(&mut self) {
+        self.schedule = Some(self.compute_schedule());
+    }
+
+    /// Schedules the dynamic entries and returns all entries of the month
+    /// together with where they come from.
+    #[must_use]
+    pub fn scheduled_entries(&self) -> Vec<(EntrySource, Entry)> {
+        match &self.schedule {
+            Some(schedule) => schedule.entries.clone(),
+            None => self.compute_schedule().entries,
+        }
+    }
+
+    /// Returns the dynamic work, that could not be placed in the month.
+    ///
+    /// For every action there is at most one entry with the total duration
+    /// that is missing.
+    #[must_use]
+    pub fn unscheduled_work(&self) -> Vec<UnscheduledWork> {
+        match &self.schedule {
+            Some(schedule) => schedule.unscheduled.clone(),
+            None => self.compute_schedule().unscheduled,
+        }
+    }
+
+    /// Returns the transfer that ends up in the time sheet.
+    ///
+    /// With [`UnscheduledPolicy::Transfer`] the work that could not be scheduled is
+    /// subtracted through the transfer, so the worked time and the transfer still
+    /// add up to the expected working time (worked + previous - next = expected).
+    #[must_use]
+    pub fn scheduled_transfer(&self) -> Transfer {
+        if self.options.unscheduled != UnscheduledPolicy::Transfer {
+            return self.transfer();
+        }
+
+        let missing = self
+            .unscheduled_work()
+            .iter()
+            .map(UnscheduledWork::duration)
+            .sum::<WorkingDuration>();
 
-        MonthFile::new(self.year, self.month, self.transfer(), entries)
+        self.transfer() + Transfer::negative(missing)
+    }
+
+    /// Schedules the dynamic entries and returns all entries of the month.
+    pub fn to_month_file(&self) -> MonthFile {
+        let entries = self
+            .scheduled_entries()
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .collect();
+
+        MonthFile::new(self.year, self.month, self.scheduled_transfer(), entries)
     }
 
     pub fn actions_that_overflow(&self) -> impl Iterator<Item = &str> + '_ {
         let character_limit = 25;
-        self.entries
-            .iter()
+        self.entries()
             .map(|e| e.action())
             .chain(self.dynamic_entries.iter().map(|e| e.action()))
             .filter(move |a| a.len() > character_limit)
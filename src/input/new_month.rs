@@ -0,0 +1,185 @@
+use core::fmt;
+use std::fs::File;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use anyhow::Context;
+use log::warn;
+
+use crate::input::overtime::following;
+use crate::input::scheduler::Strategy;
+use crate::input::toml_input;
+use crate::input::OvertimeLedger;
+use crate::time::holiday::HolidayCalendar;
+use crate::time::{Date, Month, WorkingDuration, Year};
+use crate::utils;
+
+/// The month file for the month after a previous one, which can be written
+/// as a commented toml file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthScaffold {
+    year: Year,
+    month: Month,
+    department: String,
+    strategy: Option<Strategy>,
+    signature_date: Option<Date>,
+    previous_month: WorkingDuration,
+    /// Days on which one can not work, together with the name of the
+    /// holiday or closure.
+    absences: Vec<(RangeInclusive<usize>, String)>,
+}
+
+impl MonthScaffold {
+    /// Makes the month after `previous`, with the same department and strategy.
+    ///
+    /// The overtime of the previous month is transferred into the new month,
+    /// the signature is dated on its last workday and the public holidays and
+    /// closures are added as absences.
+    pub fn from_previous(
+        global: &toml_input::Global,
+        previous: &toml_input::Month,
+    ) -> anyhow::Result<Self> {
+        let general = previous.general();
+        let (year, month) = following((general.year(), general.month()));
+
+        let ledger = OvertimeLedger::from_months(global, general.department(), [previous.clone()])?;
+        let carried = ledger
+            .carried(general.year(), general.month())
+            .unwrap_or_default();
+
+        let previous_month = if carried.is_positive() {
+            carried.next()
+        } else {
+            warn!(
+                "{}-{:02} is missing {}, which can not be transferred to the next month",
+                general.year(),
+                general.month().as_usize(),
+                carried.previous()
+            );
+            WorkingDuration::default()
+        };
+
+        let holidays = global.holidays();
+
+        Ok(Self {
+            year,
+            month,
+            department: general.department().to_string(),
+            strategy: general.strategy(),
+            signature_date: year.days_in(month).rfind(|date| date.is_workday(&holidays)),
+            previous_month,
+            absences: Self::absences_in(year, month, &holidays),
+        })
+    }
+
+    /// Reads the global and the previous month file, see [`MonthScaffold::from_previous`].
+    pub fn from_files(
+        global: impl AsRef<Path>,
+        previous: impl AsRef<Path>,
+    ) -> anyhow::Result<Self> {
+        let global: toml_input::Global = utils::toml_from_reader(File::open(global.as_ref())?)
+            .with_context(|| format!("failed to parse `{}`", global.as_ref().display()))?;
+        let previous: toml_input::Month =
+            utils::toml_from_reader(File::open(previous.as_ref())?)
+                .with_context(|| format!("failed to parse `{}`", previous.as_ref().display()))?;
+
+        Self::from_previous(&global, &previous)
+    }
+
+    /// Returns the days on which one can not work, consecutive days of the same
+    /// holiday or closure are merged.
+    fn absences_in(
+        year: Year,
+        month: Month,
+        holidays: &HolidayCalendar,
+    ) -> Vec<(RangeInclusive<usize>, String)> {
+        let mut result: Vec<(RangeInclusive<usize>, String)> = Vec::new();
+
+        for date in year.days_in(month) {
+            let name = if let Some(closure) = holidays.closure_on(date) {
+                Some(closure.name().unwrap_or("Schließung").to_string())
+            } else if holidays.is_holiday(date) {
+                holidays
+                    .state()
+                    .get_holiday_entry(date)
+                    .map(|entry| entry.name().to_string())
+            } else {
+                None
+            };
+
+            if let Some(name) = name {
+                match result.last_mut() {
+                    Some((days, last_name))
+                        if *days.end() + 1 == date.day() && *last_name == name =>
+                    {
+                        *days = *days.start()..=date.day();
+                    }
+                    _ => result.push((date.day()..=date.day(), name)),
+                }
+            }
+        }
+
+        result
+    }
+
+    #[must_use]
+    pub const fn year(&self) -> Year {
+        self.year
+    }
+
+    #[must_use]
+    pub const fn month(&self) -> Month {
+        self.month
+    }
+
+    /// Parses the scaffold like a month file.
+    pub fn to_month(&self) -> anyhow::Result<toml_input::Month> {
+        Ok(toml::from_str(&self.to_string())?)
+    }
+}
+
+impl fmt::Display for MonthScaffold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[general]")?;
+        writeln!(f, "month = {}", self.month.as_usize())?;
+        writeln!(f, "year = {}", self.year)?;
+        writeln!(f, "department = \"{}\"", self.department)?;
+        if let Some(strategy) = self.strategy {
+            writeln!(f, "strategy = \"{}\"", strategy)?;
+        }
+
+        if let Some(date) = self.signature_date {
+            writeln!(f)?;
+            writeln!(f, "# the date under the signature")?;
+            writeln!(f, "[general.signature]")?;
+            writeln!(f, "date = \"{}\"", date)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "# the overtime from the previous month")?;
+        writeln!(f, "[transfer]")?;
+        writeln!(f, "previous_month = \"{}\"", self.previous_month)?;
+        writeln!(f, "next_month = \"00:00\"")?;
+
+        for (days, name) in &self.absences {
+            writeln!(f)?;
+            writeln!(f, "# {}", name)?;
+            if days.start() == days.end() {
+                writeln!(f, "[absence.{}]", days.start())?;
+            } else {
+                writeln!(f, "[absence.{}-{}]", days.start(), days.end())?;
+            }
+            writeln!(f, "start = \"00:00\"")?;
+            writeln!(f, "end = \"23:59\"")?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "# [entries.1]")?;
+        writeln!(f, "# action = \"Tutorium\"")?;
+        writeln!(f, "# start = \"10:00\"")?;
+        writeln!(f, "# end = \"12:00\"")?;
+        writeln!(f)?;
+        writeln!(f, "# [dynamic.\"Vorbereitung\"]")?;
+        writeln!(f, "# duration = \"10:00\"")
+    }
+}
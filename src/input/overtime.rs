@@ -0,0 +1,369 @@
+use core::fmt;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::input::toml_input::{self, Transfer};
+use crate::input::Config;
+use crate::time::{Month, WorkingDuration, Year};
+
+/// A problem with the transfers between the months of a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum OvertimeProblem {
+    #[error("{}-{:02} transfers {next} to the next month, but the next month starts with {previous} from the previous month", .year, .month.as_usize())]
+    Mismatch {
+        year: Year,
+        month: Month,
+        next: WorkingDuration,
+        previous: WorkingDuration,
+    },
+    #[error("{}-{:02} transfers {stated} to the next month, but the entries of the month result in {actual}", .year, .month.as_usize())]
+    Balance {
+        year: Year,
+        month: Month,
+        stated: WorkingDuration,
+        actual: Transfer,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct OvertimeMonth {
+    expected: WorkingDuration,
+    worked: WorkingDuration,
+    transfer: Transfer,
+    is_filled: bool,
+    path: Option<PathBuf>,
+}
+
+impl OvertimeMonth {
+    /// How much more (or less) has been worked than the contract requires.
+    fn surplus(&self) -> Transfer {
+        Transfer::new(self.expected, self.worked).normalized()
+    }
+
+    /// The time that should be transferred to the next month, which includes
+    /// the time from the previous month.
+    fn carried(&self) -> Transfer {
+        Transfer::new(self.expected, self.worked + self.transfer.previous()).normalized()
+    }
+}
+
+/// Returns the year and month after the given one.
+pub(crate) const fn following((year, month): (Year, Month)) -> (Year, Month) {
+    if let Month::December = month {
+        (year.next(), Month::January)
+    } else {
+        (year, month.next())
+    }
+}
+
+/// Keeps track of the overtime of a contract over consecutive months.
+#[derive(Debug, Clone)]
+pub struct OvertimeLedger {
+    department: String,
+    months: BTreeMap<(Year, Month), OvertimeMonth>,
+}
+
+impl OvertimeLedger {
+    #[must_use]
+    pub fn new(department: impl Into<String>) -> Self {
+        Self {
+            department: department.into(),
+            months: BTreeMap::new(),
+        }
+    }
+
+    /// Makes a ledger from the month files of the contract.
+    ///
+    /// Months without a transfer take the time from the previous month and
+    /// transfer whatever has been worked in addition to the next month.
+    /// Months of other departments are ignored.
+    pub fn from_months(
+        global: &toml_input::Global,
+        department: &str,
+        months: impl IntoIterator<Item = toml_input::Month>,
+    ) -> anyhow::Result<Self> {
+        Self::from_files(
+            global,
+            department,
+            months.into_iter().map(|month| (None, month)),
+        )
+    }
+
+    fn from_files(
+        global: &toml_input::Global,
+        department: &str,
+        files: impl IntoIterator<Item = (Option<PathBuf>, toml_input::Month)>,
+    ) -> anyhow::Result<Self> {
+        let mut files = files
+            .into_iter()
+            .filter(|(_, month)| month.general().department() == department)
+            .collect::<Vec<_>>();
+        files.sort_by_key(|(_, month)| (month.general().year(), month.general().month()));
+
+        let mut ledger = Self::new(department);
+
+        for (path, mut month) in files {
+            let year = month.general().year();
+            let month_number = month.general().month();
+            let is_filled = month.transfer().is_none();
+
+            if is_filled {
+                let previous = ledger
+                    .months
+                    .iter()
+                    .find(|(key, _)| following(**key) == (year, month_number))
+                    .map(|(_, previous)| previous.transfer.next())
+                    .unwrap_or_default();
+
+                month.set_transfer(Transfer::negative(previous));
+            }
+
+            let config = Config::try_from_toml(month, global.clone())?
+                .build()
+                .map_err(|error| {
+                    error.context(format!(
+                        "failed to build the month {}-{:02}",
+                        year,
+                        month_number.as_usize()
+                    ))
+                })?;
+
+            ledger.add(&config, is_filled);
+
+            if let Some(entry) = ledger.months.get_mut(&(year, month_number)) {
+                entry.path = path;
+            }
+        }
+
+        Ok(ledger)
+    }
+
+    /// Reads all month files in the directory and makes a ledger for each
+    /// contract with months in them.
+    pub fn from_directory(
+        global: impl AsRef<Path>,
+        directory: impl AsRef<Path>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let global_path = global.as_ref();
+        let global = toml_input::Global::from_file(global_path)?;
+
+        let files = toml_input::Month::from_directory(directory, global_path)?;
+
+        let departments = files
+            .iter()
+            .map(|(_, month)| month.general().department().to_string())
+            .collect::<BTreeSet<_>>();
+
+        departments
+            .into_iter()
+            .map(|department| {
+                Self::from_files(
+                    &global,
+                    &department,
+                    files
+                        .iter()
+                        .map(|(path, month)| (Some(path.clone()), month.clone())),
+                )
+            })
+            .collect()
+    }
+
+    /// Adds the month, if `is_filled` is true the time worked in addition is
+    /// transferred to the next month.
+    pub fn add(&mut self, config: &Config, is_filled: bool) {
+        let month = config.month();
+        let expected = month.real_expected_working_duration();
+        let worked = month
+            .to_month_file()
+            .entries()
+            .iter()
+            .map(|entry| entry.work_duration())
+            .sum::<WorkingDuration>();
+
+        let mut transfer = month.transfer();
+        if is_filled {
+            let carried = Transfer::new(expected, worked + transfer.previous()).normalized();
+            transfer = Transfer::new(
+                transfer.previous(),
+                if carried.is_positive() {
+                    carried.next()
+                } else {
+                    WorkingDuration::default()
+                },
+            );
+        }
+
+        self.months.insert(
+            (month.year(), month.month()),
+            OvertimeMonth {
+                expected,
+                worked,
+                transfer,
+                is_filled,
+                path: None,
+            },
+        );
+    }
+
+    #[must_use]
+    pub fn department(&self) -> &str {
+        &self.department
+    }
+
+    /// Returns the transfer of the month, which has been filled in if the
+    /// month file does not have one.
+    #[must_use]
+    pub fn transfer(&self, year: Year, month: Month) -> Option<Transfer> {
+        self.months.get(&(year, month)).map(|month| month.transfer)
+    }
+
+    /// Returns the time that the entries of the month carry over to the next
+    /// month, including the time from the previous month.
+    #[must_use]
+    pub fn carried(&self, year: Year, month: Month) -> Option<Transfer> {
+        self.months.get(&(year, month)).map(OvertimeMonth::carried)
+    }
+
+    /// Returns the months for which the transfer has been filled in.
+    pub fn filled_transfers(&self) -> impl Iterator<Item = (Year, Month, Transfer)> + '_ {
+        self.months
+            .iter()
+            .filter(|(_, month)| month.is_filled)
+            .map(|((year, month), entry)| (*year, *month, entry.transfer))
+    }
+
+    /// The overtime after the last month, a negative balance means that less
+    /// has been worked than required.
+    #[must_use]
+    pub fn balance(&self) -> Transfer {
+        self.running_balance()
+            .last()
+            .map(|(_, balance)| balance)
+            .unwrap_or_default()
+    }
+
+    /// Returns the balance after each month, starting with the time from the
+    /// month before the first one.
+    fn running_balance(&self) -> impl Iterator<Item = ((Year, Month), Transfer)> + '_ {
+        let start = self
+            .months
+            .values()
+            .next()
+            .map(|month| Transfer::positive(month.transfer.previous()))
+            .unwrap_or_default();
+
+        self.months.iter().scan(start, |balance, (key, month)| {
+            *balance += month.surplus();
+            Some((*key, *balance))
+        })
+    }
+
+    /// Checks that the transfer of each month matches its entries and that
+    /// consecutive months transfer the same time.
+    #[must_use]
+    pub fn problems(&self) -> Vec<OvertimeProblem> {
+        let mut problems = Vec::new();
+
+        for (&(year, month), entry) in &self.months {
+            let carried = entry.carried();
+            if carried != Transfer::positive(entry.transfer.next()) {
+                problems.push(OvertimeProblem::Balance {
+                    year,
+                    month,
+                    stated: entry.transfer.next(),
+                    actual: carried,
+                });
+            }
+
+            if let Some(next) = self.months.get(&following((year, month))) {
+                if entry.transfer.next() != next.transfer.previous() {
+                    problems.push(OvertimeProblem::Mismatch {
+                        year,
+                        month,
+                        next: entry.transfer.next(),
+                        previous: next.transfer.previous(),
+                    });
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Appends the filled in transfers to the month files that do not have one.
+    ///
+    /// Returns the paths of the files that have been changed.
+    pub fn fill_in(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let mut result = Vec::new();
+
+        for month in self.months.values().filter(|month| month.is_filled) {
+            let Some(path) = &month.path else {
+                continue;
+            };
+
+            append_transfer(path, month.transfer)?;
+            result.push(path.clone());
+        }
+
+        Ok(result)
+    }
+}
+
+fn append_transfer(path: &Path, transfer: Transfer) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new().append(true).open(path)?;
+
+    write!(
+        file,
+        "\n[transfer]\nprevious_month = \"{}\"\nnext_month = \"{}\"\n",
+        transfer.previous(),
+        transfer.next()
+    )?;
+
+    Ok(())
+}
+
+impl fmt::Display for OvertimeLedger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "overtime ({})", self.department)?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<7}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}",
+            "Month", "Expected", "Worked", "Previous", "Next", "Surplus", "Balance"
+        )?;
+
+        for ((year, month), balance) in self.running_balance() {
+            let entry = &self.months[&(year, month)];
+            writeln!(
+                f,
+                "{:04}-{:02}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}",
+                year,
+                month.as_usize(),
+                entry.expected.to_string(),
+                entry.worked.to_string(),
+                entry.transfer.previous().to_string(),
+                format!(
+                    "{}{}",
+                    entry.transfer.next(),
+                    if entry.is_filled { "*" } else { "" }
+                ),
+                entry.surplus().to_string(),
+                balance.to_string()
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{:>8}  {:>8}", "balance:", self.balance().to_string())?;
+
+        if self.months.values().any(|month| month.is_filled) {
+            writeln!(f)?;
+            writeln!(f, "* filled in, because the month has no transfer")?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::input::scheduler::{Scheduler, SchedulerOptions};
+use crate::time::holiday::HolidayCalendar;
 use crate::time::{Date, WorkingDuration};
 use crate::{min, working_duration};
 
@@ -9,6 +10,7 @@ pub struct AbsenceScheduler<F> {
     f: F,
     should_mix: bool,
     limit: WorkingDuration,
+    holidays: HolidayCalendar,
 }
 
 impl<F> AbsenceScheduler<F>
@@ -16,11 +18,12 @@ where
     F: Fn(Date) -> WorkingDuration,
 {
     #[must_use]
-    pub const fn new(f: F, options: &SchedulerOptions) -> Self {
+    pub fn new(f: F, options: &SchedulerOptions) -> Self {
         Self {
             f,
             should_mix: options.should_schedule_with_absences,
             limit: options.daily_limit,
+            holidays: options.holidays.clone(),
         }
     }
 }
@@ -31,9 +34,11 @@ where
 {
     fn has_time_for(&self, date: Date, wanted_duration: WorkingDuration) -> WorkingDuration {
         let absent_duration = (self.f)(date);
+        // on some days like christmas eve, one only works half of the day
+        let limit = self.holidays.available_time_on(date, self.limit);
 
-        // the min prevents an underflow, when absent_duration > self.limit
-        let remaining = self.limit - min!(self.limit, absent_duration);
+        // the min prevents an underflow, when absent_duration > limit
+        let remaining = limit - min!(limit, absent_duration);
 
         if !self.should_mix && absent_duration > working_duration!(00:00) {
             working_duration!(00:00)
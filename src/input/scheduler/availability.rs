@@ -0,0 +1,171 @@
+use serde::Deserialize;
+
+use crate::input::scheduler::{Scheduler, SchedulerOptions};
+use crate::min;
+use crate::time::{Date, TimeSpan, WeekDay, WorkingDuration};
+use crate::time_stamp;
+
+/// The times of the week in which one can work.
+///
+/// Days without any time spans are not available at all.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Availability {
+    #[serde(default)]
+    monday: Vec<TimeSpan>,
+    #[serde(default)]
+    tuesday: Vec<TimeSpan>,
+    #[serde(default)]
+    wednesday: Vec<TimeSpan>,
+    #[serde(default)]
+    thursday: Vec<TimeSpan>,
+    #[serde(default)]
+    friday: Vec<TimeSpan>,
+    #[serde(default)]
+    saturday: Vec<TimeSpan>,
+    #[serde(default)]
+    sunday: Vec<TimeSpan>,
+}
+
+impl Availability {
+    /// Returns the time spans in which one can work on that day of the week.
+    #[must_use]
+    pub fn windows_on(&self, week_day: WeekDay) -> &[TimeSpan] {
+        match week_day {
+            WeekDay::Monday => &self.monday,
+            WeekDay::Tuesday => &self.tuesday,
+            WeekDay::Wednesday => &self.wednesday,
+            WeekDay::Thursday => &self.thursday,
+            WeekDay::Friday => &self.friday,
+            WeekDay::Saturday => &self.saturday,
+            WeekDay::Sunday => &self.sunday,
+        }
+    }
+
+    /// Returns the time spans of the day, in which one can not work.
+    #[must_use]
+    pub fn unavailable_on(&self, week_day: WeekDay) -> Vec<TimeSpan> {
+        let mut windows = self.windows_on(week_day).to_vec();
+        windows.sort();
+
+        let mut result = Vec::with_capacity(windows.len() + 1);
+        let mut start = time_stamp!(00:00);
+        for window in windows {
+            if start < window.start() {
+                result.push(TimeSpan::new(start, window.start()));
+            }
+
+            start = window.end();
+        }
+
+        let end_of_day = time_stamp!(23:59);
+        if start < end_of_day {
+            result.push(TimeSpan::new(start, end_of_day));
+        }
+
+        result
+    }
+
+    /// Checks that the time spans of a day do not overlap.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for week_day in WeekDay::week_days() {
+            let mut windows = self.windows_on(week_day).to_vec();
+            windows.sort();
+
+            for pair in windows.windows(2) {
+                if pair[0].end() > pair[1].start() {
+                    return Err(anyhow::anyhow!(
+                        "the available times {} and {} on {} overlap",
+                        pair[0],
+                        pair[1],
+                        week_day
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A scheduler that only schedules work on days where one is available and
+/// not more than fits into the longest free time span of that day.
+///
+/// The function returns the longest time span of a day, that is neither taken
+/// by entries or absences nor outside of the availability.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvailabilityScheduler<F> {
+    f: F,
+    is_restricted: bool,
+}
+
+impl<F> AvailabilityScheduler<F>
+where
+    F: Fn(Date) -> WorkingDuration,
+{
+    #[must_use]
+    pub fn new(f: F, options: &SchedulerOptions) -> Self {
+        Self {
+            f,
+            is_restricted: options.availability.is_some(),
+        }
+    }
+}
+
+impl<F> Scheduler for AvailabilityScheduler<F>
+where
+    F: Fn(Date) -> WorkingDuration,
+{
+    fn has_time_for(&self, date: Date, wanted_duration: WorkingDuration) -> WorkingDuration {
+        if self.is_restricted {
+            min!(wanted_duration, (self.f)(date))
+        } else {
+            wanted_duration
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[must_use]
+    fn availability() -> Availability {
+        toml::from_str(concat!(
+            "tuesday = [\"14:00-16:00\", \"08:00-10:30\"]\n",
+            "thursday = [\"14:00-19:00\"]\n",
+        ))
+        .expect("toml should be valid")
+    }
+
+    #[test]
+    fn test_unavailable_on() {
+        let availability = availability();
+
+        assert_eq!(
+            availability.unavailable_on(WeekDay::Tuesday),
+            vec![
+                TimeSpan::new(time_stamp!(00:00), time_stamp!(08:00)),
+                TimeSpan::new(time_stamp!(10:30), time_stamp!(14:00)),
+                TimeSpan::new(time_stamp!(16:00), time_stamp!(23:59)),
+            ]
+        );
+        assert_eq!(
+            availability.unavailable_on(WeekDay::Monday),
+            vec![TimeSpan::new(time_stamp!(00:00), time_stamp!(23:59))]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(availability().validate().is_ok());
+
+        let availability: Availability =
+            toml::from_str("monday = [\"08:00-12:00\", \"11:00-13:00\"]\n").unwrap();
+        assert_eq!(
+            availability.validate().unwrap_err().to_string(),
+            "the available times 08:00 - 12:00 and 11:00 - 13:00 on Monday overlap".to_string()
+        );
+    }
+}
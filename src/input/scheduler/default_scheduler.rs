@@ -1,19 +1,21 @@
 use crate::input::scheduler::{
-    AbsenceScheduler, DailyLimiter, FixedScheduler, MonthScheduler, Scheduler, SchedulerOptions,
-    WorkdayScheduler,
+    AbsenceScheduler, AvailabilityScheduler, DailyLimiter, FixedScheduler, MonthScheduler,
+    Scheduler, SchedulerOptions, WeeklyLimiter, WorkdayScheduler,
 };
 use crate::input::Month;
 use crate::input::Transfer;
 use crate::time::{Date, WorkingDuration};
-use crate::working_duration;
+use crate::{min, working_duration};
 
 #[derive(Debug, Clone)]
 pub struct DefaultScheduler<F> {
     scheduler: (
         WorkdayScheduler,
+        AvailabilityScheduler<F>,
         FixedScheduler<F>,
         AbsenceScheduler<F>,
         DailyLimiter,
+        WeeklyLimiter<F>,
     ),
     month_scheduler: MonthScheduler,
 }
@@ -23,20 +25,32 @@ impl<'a> DefaultScheduler<Box<dyn Fn(Date) -> WorkingDuration + 'a>> {
     pub fn new(month: &'a Month, options: &SchedulerOptions) -> Self {
         Self {
             scheduler: (
-                WorkdayScheduler::new(),
+                WorkdayScheduler::new(options),
+                AvailabilityScheduler::new(
+                    Box::new(|date| month.longest_free_span_on(date)),
+                    options,
+                ),
                 FixedScheduler::new(Box::new(|date| month.working_time_on_day(date)), options),
                 AbsenceScheduler::new(Box::new(|date| month.absence_time_on_day(date)), options),
                 DailyLimiter::new(options),
+                WeeklyLimiter::new(Box::new(|date| month.working_time_on_day(date)), options),
             ),
             month_scheduler: MonthScheduler::new_with_available_time(
                 month.year(),
                 month.month(),
                 month.expected_working_duration(),
                 |date| {
-                    if date.is_workday() {
-                        options
-                            .daily_limit
-                            .saturating_sub(month.absence_time_on_day(date))
+                    if date.is_workday(&options.holidays) && !month.is_blocked_day(date) {
+                        let available = options
+                            .holidays
+                            .available_time_on(date, options.daily_limit)
+                            .saturating_sub(month.absence_time_on_day(date));
+
+                        if options.availability.is_some() {
+                            min!(available, month.longest_free_span_on(date))
+                        } else {
+                            available
+                        }
                     } else {
                         working_duration!(00:00)
                     }
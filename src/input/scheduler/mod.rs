@@ -1,20 +1,26 @@
 mod absence_scheduler;
+mod availability;
 mod daily_limiter;
 mod default_scheduler;
 mod fixed_scheduler;
 mod month_scheduler;
 mod scheduler_options;
+mod slot_allocator;
 mod time_span;
+mod weekly_limiter;
 mod work_schedule;
 mod workday_scheduler;
 
 pub use absence_scheduler::*;
+pub use availability::*;
 pub use daily_limiter::*;
 pub use default_scheduler::*;
 pub use fixed_scheduler::*;
 pub use month_scheduler::*;
 pub use scheduler_options::*;
+pub use slot_allocator::*;
 pub use time_span::*;
+pub use weekly_limiter::*;
 pub use work_schedule::*;
 pub use workday_scheduler::*;
 
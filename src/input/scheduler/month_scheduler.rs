@@ -2,6 +2,7 @@ use log::debug;
 
 use crate::input::scheduler::{Scheduler, TimeSpanScheduler};
 use crate::input::toml_input::Transfer;
+use crate::time::holiday::HolidayCalendar;
 use crate::time::{Date, DurationExt, Month, WorkingDuration, Year};
 use crate::utils::{self, ArrayExt};
 use crate::working_duration;
@@ -31,7 +32,7 @@ impl MonthScheduler {
 
     pub fn new(year: Year, month: Month, maximum_time: WorkingDuration) -> Self {
         Self::new_with_available_time(year, month, maximum_time, |date| {
-            if date.is_workday() {
+            if date.is_workday(&HolidayCalendar::default()) {
                 working_duration!(00:01)
             } else {
                 working_duration!(00:00)
@@ -1,15 +1,20 @@
 use std::str::FromStr;
 
+use derive_more::Display;
 use serde::Deserialize;
 
-use crate::time::WorkingDuration;
-use crate::working_duration;
+use crate::input::scheduler::Availability;
+use crate::time::holiday::HolidayCalendar;
+use crate::time::{TimeSpan, WorkingDuration};
+use crate::{time_stamp, working_duration};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Display)]
 #[serde(try_from = "String")]
 pub enum Strategy {
     #[default]
+    #[display("first-come-first-serve")]
     FirstComeFirstServe,
+    #[display("proportional")]
     Proportional,
 }
 
@@ -33,8 +38,42 @@ impl TryFrom<String> for Strategy {
     }
 }
 
+/// What should happen with dynamic work that could not be placed in the month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum UnscheduledPolicy {
+    /// Logs a warning for every action that could not be scheduled completely.
+    #[default]
+    Warn,
+    /// Fails to build the config.
+    Error,
+    /// Balances the remaining time through the transfer from the previous month.
+    Transfer,
+}
+
+impl FromStr for UnscheduledPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            "transfer" => Ok(Self::Transfer),
+            _ => Err(anyhow::anyhow!("Unknown unscheduled policy: {}", string)),
+        }
+    }
+}
+
+impl TryFrom<String> for UnscheduledPolicy {
+    type Error = <Self as FromStr>::Err;
+
+    fn try_from(string: String) -> Result<Self, <Self as TryFrom<String>>::Error> {
+        Self::from_str(&string)
+    }
+}
+
 /// Options to configure the default scheduler.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub struct SchedulerOptions {
     /// If this is set to `true`, tasks can be scheduled on days where the user
@@ -47,8 +86,25 @@ pub struct SchedulerOptions {
     pub should_schedule_with_absences: bool,
     /// The maximum duration that can be scheduled on a single day.
     pub daily_limit: WorkingDuration,
+    /// The maximum duration that can be worked in a single week, if any.
+    pub weekly_limit: Option<WorkingDuration>,
+    /// The maximum duration that can be worked on a single day, including the
+    /// fixed entries.
+    pub maximum_work_per_day: WorkingDuration,
     /// The strategy to use for scheduling tasks.
     pub strategy: Strategy,
+    /// What happens with the work that could not be scheduled.
+    pub unscheduled: UnscheduledPolicy,
+    /// The time of the day in which dynamic entries can be placed.
+    pub working_hours: TimeSpan,
+    /// The times of the week in which one can work, if they are restricted.
+    pub availability: Option<Availability>,
+    /// The shortest part into which a dynamic entry can be split.
+    pub minimum_chunk: WorkingDuration,
+    /// The parts of a split dynamic entry are rounded down to a multiple of this.
+    pub rounding: WorkingDuration,
+    /// The public holidays on which no work is scheduled.
+    pub holidays: HolidayCalendar,
 }
 
 impl Default for SchedulerOptions {
@@ -57,7 +113,15 @@ impl Default for SchedulerOptions {
             should_schedule_with_fixed_entries: false,
             should_schedule_with_absences: false,
             daily_limit: working_duration!(06:00),
+            weekly_limit: None,
+            maximum_work_per_day: working_duration!(08:00),
             strategy: Default::default(),
+            unscheduled: Default::default(),
+            working_hours: TimeSpan::new(time_stamp!(06:00), time_stamp!(22:00)),
+            availability: None,
+            minimum_chunk: working_duration!(00:00),
+            rounding: working_duration!(00:01),
+            holidays: HolidayCalendar::default(),
         }
     }
 }
@@ -0,0 +1,237 @@
+use crate::time::{TimeSpan, TimeStamp, WorkingDuration};
+
+/// Finds free time spans on a single day, so that entries do not overlap.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotAllocator {
+    working_hours: TimeSpan,
+    occupied: Vec<TimeSpan>,
+}
+
+impl SlotAllocator {
+    #[must_use]
+    pub fn new(working_hours: TimeSpan) -> Self {
+        Self {
+            working_hours,
+            occupied: Vec::new(),
+        }
+    }
+
+    /// Marks the time span as taken, so no slot will overlap with it.
+    pub fn occupy(&mut self, span: TimeSpan) {
+        self.occupied.push(span);
+    }
+
+    #[must_use]
+    fn is_free(&self, start: u16, end: u16) -> bool {
+        start >= self.working_hours.start().as_mins()
+            && end <= self.working_hours.end().as_mins()
+            && self
+                .occupied
+                .iter()
+                .all(|span| end <= span.start().as_mins() || span.end().as_mins() <= start)
+    }
+
+    /// Returns the times at which a slot could start, in the order they should be tried.
+    ///
+    /// A slot can only start at the preferred time, at the start of the working hours
+    /// or directly after an occupied span. The candidates after the preferred time are
+    /// tried first, then the earlier ones.
+    #[must_use]
+    fn candidates(&self, preferred: TimeStamp) -> Vec<TimeStamp> {
+        let mut candidates = self
+            .occupied
+            .iter()
+            .map(TimeSpan::end)
+            .chain([self.working_hours.start()])
+            .collect::<Vec<_>>();
+        candidates.sort();
+
+        let (before, after): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .partition(|candidate| *candidate < preferred);
+
+        [preferred].into_iter().chain(after).chain(before).collect()
+    }
+
+    /// Returns how many minutes are free, starting at `start`.
+    #[must_use]
+    fn free_mins_from(&self, start: u16) -> u16 {
+        let is_occupied = self
+            .occupied
+            .iter()
+            .any(|span| span.start().as_mins() <= start && start < span.end().as_mins());
+
+        if start < self.working_hours.start().as_mins() || is_occupied {
+            return 0;
+        }
+
+        self.occupied
+            .iter()
+            .map(|span| span.start().as_mins())
+            .filter(|span_start| *span_start > start)
+            .chain([self.working_hours.end().as_mins()])
+            .min()
+            .map_or(0, |end| end.saturating_sub(start))
+    }
+
+    /// Returns the length of the longest free slot.
+    #[must_use]
+    pub fn longest_free(&self) -> WorkingDuration {
+        self.occupied
+            .iter()
+            .map(TimeSpan::end)
+            .chain([self.working_hours.start()])
+            .map(|start| self.free_mins_from(start.as_mins()))
+            .max()
+            .map_or_else(WorkingDuration::default, WorkingDuration::from_mins)
+    }
+
+    /// Returns the first free slot of the given length and marks it as taken.
+    ///
+    /// The slot will start at `preferred` if possible, otherwise the first free slot
+    /// after it is used and only if there is none, the earlier slots are considered.
+    pub fn allocate(&mut self, length: WorkingDuration, preferred: TimeStamp) -> Option<TimeSpan> {
+        let start = self.candidates(preferred).into_iter().find(|start| {
+            let start = start.as_mins();
+            self.is_free(start, start + length.as_mins())
+        })?;
+
+        let span = TimeSpan::new(start, start + length);
+        self.occupy(span);
+
+        Some(span)
+    }
+
+    /// Like [`SlotAllocator::allocate`], but the slot can be shorter than `length`
+    /// if there is no slot that is long enough.
+    ///
+    /// This is used for splitting a task into multiple parts.
+    pub fn allocate_up_to(
+        &mut self,
+        length: WorkingDuration,
+        preferred: TimeStamp,
+    ) -> Option<TimeSpan> {
+        if let Some(span) = self.allocate(length, preferred) {
+            return Some(span);
+        }
+
+        let (start, free) = self
+            .candidates(preferred)
+            .into_iter()
+            .map(|start| (start, self.free_mins_from(start.as_mins())))
+            .find(|(_, free)| *free > 0)?;
+
+        let span = TimeSpan::new(start, start + WorkingDuration::from_mins(free));
+        self.occupy(span);
+
+        Some(span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{time_stamp, working_duration};
+
+    #[must_use]
+    fn allocator() -> SlotAllocator {
+        SlotAllocator::new(TimeSpan::new(time_stamp!(08:00), time_stamp!(18:00)))
+    }
+
+    #[test]
+    fn test_allocate_preferred() {
+        let mut allocator = allocator();
+
+        assert_eq!(
+            allocator.allocate(working_duration!(02:00), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(10:00), time_stamp!(12:00)))
+        );
+        // the preferred slot is taken, so the next one is used:
+        assert_eq!(
+            allocator.allocate(working_duration!(02:00), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(12:00), time_stamp!(14:00)))
+        );
+    }
+
+    #[test]
+    fn test_allocate_around_occupied() {
+        let mut allocator = allocator();
+        allocator.occupy(TimeSpan::new(time_stamp!(09:30), time_stamp!(11:15)));
+        allocator.occupy(TimeSpan::new(time_stamp!(12:00), time_stamp!(18:00)));
+
+        assert_eq!(
+            allocator.allocate(working_duration!(00:45), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(11:15), time_stamp!(12:00)))
+        );
+        // nothing is free after the preferred start, so earlier slots are used:
+        assert_eq!(
+            allocator.allocate(working_duration!(01:30), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(08:00), time_stamp!(09:30)))
+        );
+        assert_eq!(
+            allocator.allocate(working_duration!(00:01), time_stamp!(10:00)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_allocate_outside_working_hours() {
+        let mut allocator = allocator();
+
+        assert_eq!(
+            allocator.allocate(working_duration!(03:00), time_stamp!(17:00)),
+            Some(TimeSpan::new(time_stamp!(08:00), time_stamp!(11:00)))
+        );
+        assert_eq!(
+            allocator.allocate(working_duration!(10:00), time_stamp!(08:00)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_allocate_up_to() {
+        let mut allocator = allocator();
+        allocator.occupy(TimeSpan::new(time_stamp!(12:00), time_stamp!(13:00)));
+
+        assert_eq!(
+            allocator.allocate_up_to(working_duration!(04:00), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(13:00), time_stamp!(17:00)))
+        );
+        assert_eq!(
+            allocator.allocate_up_to(working_duration!(03:00), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(08:00), time_stamp!(11:00)))
+        );
+        // no slot is long enough, so the task is split up:
+        assert_eq!(
+            allocator.allocate_up_to(working_duration!(03:00), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(11:00), time_stamp!(12:00)))
+        );
+        assert_eq!(
+            allocator.allocate_up_to(working_duration!(03:00), time_stamp!(10:00)),
+            Some(TimeSpan::new(time_stamp!(17:00), time_stamp!(18:00)))
+        );
+        assert_eq!(
+            allocator.allocate_up_to(working_duration!(03:00), time_stamp!(10:00)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_longest_free() {
+        let mut allocator = allocator();
+        assert_eq!(allocator.longest_free(), working_duration!(10:00));
+
+        allocator.occupy(TimeSpan::new(time_stamp!(07:00), time_stamp!(09:00)));
+        allocator.occupy(TimeSpan::new(time_stamp!(12:00), time_stamp!(13:00)));
+        assert_eq!(allocator.longest_free(), working_duration!(05:00));
+
+        allocator.occupy(TimeSpan::new(time_stamp!(15:00), time_stamp!(16:00)));
+        assert_eq!(allocator.longest_free(), working_duration!(03:00));
+
+        allocator.occupy(TimeSpan::new(time_stamp!(08:00), time_stamp!(18:00)));
+        assert_eq!(allocator.longest_free(), working_duration!(00:00));
+    }
+}
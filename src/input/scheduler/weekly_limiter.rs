@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::input::scheduler::{Scheduler, SchedulerOptions};
+use crate::min;
+use crate::time::{Date, WorkingDuration};
+
+/// A scheduler that limits the amount of work per week.
+///
+/// The limit includes the work that is already fixed on the days of the week.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeeklyLimiter<F> {
+    f: F,
+    scheduled: HashMap<Date, WorkingDuration>,
+    limit: Option<WorkingDuration>,
+}
+
+impl<F> WeeklyLimiter<F>
+where
+    F: Fn(Date) -> WorkingDuration,
+{
+    #[must_use]
+    pub fn new(f: F, options: &SchedulerOptions) -> Self {
+        Self {
+            f,
+            scheduled: HashMap::new(),
+            limit: options.weekly_limit,
+        }
+    }
+
+    #[must_use]
+    pub const fn limit(&self) -> Option<WorkingDuration> {
+        self.limit
+    }
+
+    /// Returns how much has been worked in the week of the date.
+    #[must_use]
+    fn worked_in_week(&self, date: Date) -> WorkingDuration {
+        let fixed = (date.week_start()..=date.week_end())
+            .map(|day| (self.f)(day))
+            .sum::<WorkingDuration>();
+
+        fixed
+            + self
+                .scheduled
+                .get(&date.week_start())
+                .copied()
+                .unwrap_or_default()
+    }
+}
+
+impl<F> Scheduler for WeeklyLimiter<F>
+where
+    F: Fn(Date) -> WorkingDuration,
+{
+    fn has_time_for(&self, date: Date, wanted_duration: WorkingDuration) -> WorkingDuration {
+        match self.limit {
+            Some(limit) => min!(
+                wanted_duration,
+                limit.saturating_sub(self.worked_in_week(date))
+            ),
+            None => wanted_duration,
+        }
+    }
+
+    fn schedule(&mut self, date: Date, worked: WorkingDuration) {
+        let scheduled = self.scheduled.entry(date.week_start()).or_default();
+        *scheduled += worked;
+    }
+
+    fn schedule_in_advance(&mut self, date: Date, worked: WorkingDuration) {
+        self.schedule(date, worked);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::input::strategy::{PeekableStrategy, Strategy};
-use crate::input::Scheduler;
+use crate::input::{Scheduler, Task};
 use crate::time::{Date, WorkingDuration};
-use crate::{min, working_duration};
+use crate::working_duration;
 
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct WorkSchedule {
@@ -9,6 +9,11 @@ pub(crate) struct WorkSchedule {
     start_date: Date,
     /// The end date of the work schedule (inclusive)
     end_date: Date,
+    /// The shortest part a task can be split into, if the task does not specify one.
+    minimum_chunk: WorkingDuration,
+    /// The multiple to which the parts of a task are rounded, if the task does not
+    /// specify one.
+    rounding: WorkingDuration,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -43,9 +48,54 @@ impl WorkSchedule {
         Self {
             start_date,
             end_date,
+            minimum_chunk: working_duration!(00:00),
+            rounding: working_duration!(00:01),
         }
     }
 
+    #[must_use]
+    pub(crate) fn with_chunking(
+        mut self,
+        minimum_chunk: WorkingDuration,
+        rounding: WorkingDuration,
+    ) -> Self {
+        self.minimum_chunk = minimum_chunk;
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns how much of the task should be worked, when at most `possible` can be worked.
+    ///
+    /// If the task has to be split, the part is rounded down and it is made sure that
+    /// neither the part nor the rest of the task is shorter than the minimum chunk.
+    /// The rest of the rounding will be in the last part of the task.
+    #[must_use]
+    fn chunk_of(&self, task: &Task, possible: WorkingDuration) -> WorkingDuration {
+        let duration = task.duration();
+        if possible >= duration {
+            return duration;
+        }
+
+        let minimum_chunk = task.minimum_chunk().unwrap_or(self.minimum_chunk);
+        let rounding = task.rounding().unwrap_or(self.rounding).as_mins().max(1);
+        let round_down = |duration: WorkingDuration| {
+            WorkingDuration::from_mins(duration.as_mins() - duration.as_mins() % rounding)
+        };
+
+        let mut chunk = round_down(possible);
+
+        // the remaining part of the task should not be too short either:
+        if duration - chunk < minimum_chunk {
+            chunk = round_down(duration.saturating_sub(minimum_chunk));
+        }
+
+        if chunk < minimum_chunk {
+            return working_duration!(00:00);
+        }
+
+        chunk
+    }
+
     pub fn schedule<S, P, Id, F>(
         &self,
         strategy: &mut PeekableStrategy<Id, P>,
@@ -91,7 +141,12 @@ impl WorkSchedule {
 
             let task_duration = task.duration();
             // if the task is longer than the possible work duration, we have to split it
-            let worked_duration = min!(task_duration, possible_work_duration);
+            let worked_duration = self.chunk_of(task, possible_work_duration);
+
+            // skips days where the part of the task would be too short
+            if worked_duration == working_duration!(00:00) {
+                continue;
+            }
 
             // consume the task only when it will definitely be scheduled
             let (id, task) = strategy.next_task(date).unwrap();
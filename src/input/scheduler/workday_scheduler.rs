@@ -1,21 +1,26 @@
-use crate::input::scheduler::Scheduler;
+use crate::input::scheduler::{Scheduler, SchedulerOptions};
+use crate::time::holiday::HolidayCalendar;
 use crate::time::{Date, WorkingDuration};
 use crate::working_duration;
 
 /// A scheduler that schedules work exclusively on workdays.
 #[derive(Clone, Debug, PartialEq, Default)]
-pub struct WorkdayScheduler {}
+pub struct WorkdayScheduler {
+    holidays: HolidayCalendar,
+}
 
 impl WorkdayScheduler {
     #[must_use]
-    pub const fn new() -> Self {
-        Self {}
+    pub fn new(options: &SchedulerOptions) -> Self {
+        Self {
+            holidays: options.holidays.clone(),
+        }
     }
 }
 
 impl Scheduler for WorkdayScheduler {
     fn has_time_for(&self, date: Date, wanted_duration: WorkingDuration) -> WorkingDuration {
-        if date.is_workday() {
+        if date.is_workday(&self.holidays) {
             wanted_duration
         } else {
             working_duration!(00:00)
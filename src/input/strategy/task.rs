@@ -12,6 +12,8 @@ pub struct Task {
     start: Option<TimeStamp>,
     flex: Option<usize>,
     filter: ArrayVec<Date, 31>,
+    minimum_chunk: Option<WorkingDuration>,
+    rounding: Option<WorkingDuration>,
 }
 
 impl Task {
@@ -24,6 +26,8 @@ impl Task {
             start: None,
             flex: None,
             filter: ArrayVec::new(),
+            minimum_chunk: None,
+            rounding: None,
         }
     }
 
@@ -35,6 +39,8 @@ impl Task {
             start: None,
             flex: Some(flex),
             filter: ArrayVec::new(),
+            minimum_chunk: None,
+            rounding: None,
         }
     }
 
@@ -66,6 +72,26 @@ impl Task {
         self
     }
 
+    #[must_use]
+    pub fn with_can_be_split(mut self, can_be_split: bool) -> Self {
+        self.can_be_split = can_be_split;
+        self
+    }
+
+    /// Sets the shortest part that the task can be split into.
+    #[must_use]
+    pub fn with_minimum_chunk(mut self, minimum_chunk: Option<WorkingDuration>) -> Self {
+        self.minimum_chunk = minimum_chunk;
+        self
+    }
+
+    /// Sets to which multiple the parts of the task should be rounded.
+    #[must_use]
+    pub fn with_rounding(mut self, rounding: Option<WorkingDuration>) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
     #[must_use]
     pub fn with_duration(mut self, duration: WorkingDuration) -> Self {
         self.duration = duration;
@@ -91,6 +117,16 @@ impl Task {
         self.can_be_split
     }
 
+    #[must_use]
+    pub fn minimum_chunk(&self) -> Option<WorkingDuration> {
+        self.minimum_chunk
+    }
+
+    #[must_use]
+    pub fn rounding(&self) -> Option<WorkingDuration> {
+        self.rounding
+    }
+
     #[must_use]
     pub const fn suggested_start(&self) -> Option<TimeStamp> {
         self.start
@@ -89,6 +89,20 @@ impl Absence {
         self.time_span().duration()
     }
 
+    /// Checks that an absence of a single day does not end before it starts.
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.first_day() == self.last_day() && self.start > self.end {
+            return Err(anyhow::anyhow!(
+                "the absence on day {} ends at {}, which is before its start at {}",
+                self.first_day(),
+                self.end,
+                self.start
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn to_date_absences<'a>(
         &'a self,
         make_date: impl Fn(usize) -> Date + 'a,
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::input::toml_input::SchedulerSettings;
 use crate::input::WorkingArea;
 use crate::time::{Date, WorkingDuration};
 use crate::utils::{self, MapEntry};
@@ -16,6 +17,8 @@ pub struct Contract {
     #[serde(with = "utils::serde_toml_local_date")]
     end_date: Date,
     bg_content: Option<String>,
+    #[serde(default, skip_serializing)]
+    scheduler: SchedulerSettings,
 }
 
 impl Contract {
@@ -55,6 +58,11 @@ impl Contract {
     pub fn bg_content(&self) -> Option<&str> {
         self.bg_content.as_deref()
     }
+
+    /// How dynamic entries should be scheduled for this contract.
+    pub fn scheduler(&self) -> &SchedulerSettings {
+        &self.scheduler
+    }
 }
 
 impl<'de> MapEntry<'de> for Contract {
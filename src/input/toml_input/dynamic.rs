@@ -31,6 +31,10 @@ pub struct DynamicEntry {
     pause: Option<WorkingDuration>,
     #[serde(default)]
     start: Option<TimeStamp>,
+    #[serde(default)]
+    min_chunk: Option<WorkingDuration>,
+    #[serde(default)]
+    rounding: Option<WorkingDuration>,
     #[serde(skip)]
     skip_dates: ArrayVec<Date, 31>,
 }
@@ -63,6 +67,11 @@ impl<Id> ScheduledDistribution<Id> {
     pub fn transfer_time(&self) -> Transfer {
         self.transfer_time
     }
+
+    /// Returns the tasks (or the parts of them) that could not be scheduled.
+    pub fn remaining(&self) -> &[(Id, Task)] {
+        &self.remaining
+    }
 }
 
 impl DynamicEntry {
@@ -71,9 +80,20 @@ impl DynamicEntry {
         &self.action
     }
 
+    /// Returns when the entry should start, if it has been specified.
+    #[must_use]
+    pub const fn start(&self) -> Option<TimeStamp> {
+        self.start
+    }
+
+    /// Returns how long the entry for the scheduled time takes, including the pause.
+    #[must_use]
+    pub fn length(&self, time: &ScheduledTime) -> WorkingDuration {
+        time.duration() + self.pause.unwrap_or_default()
+    }
+
     #[must_use]
     pub fn to_entry(&self, start: TimeStamp, time: ScheduledTime) -> Entry {
-        let start = self.start.unwrap_or(start);
         Entry::new(
             self.action(),
             time.date().day(),
@@ -91,12 +111,14 @@ impl DynamicEntry {
 
     #[must_use]
     pub fn to_task(&self) -> Task {
-        match self.input {
-            DynamicEntryInput::Fixed { duration } => {
-                Task::new_duration(duration).with_filter(self.skip_dates)
-            }
-            DynamicEntryInput::Flex { flex } => Task::new_flex(flex).with_filter(self.skip_dates),
-        }
+        let task = match self.input {
+            DynamicEntryInput::Fixed { duration } => Task::new_duration(duration),
+            DynamicEntryInput::Flex { flex } => Task::new_flex(flex),
+        };
+
+        task.with_filter(self.skip_dates)
+            .with_minimum_chunk(self.min_chunk)
+            .with_rounding(self.rounding)
     }
 
     pub fn distribute<Id: Copy + fmt::Debug + 'static>(
@@ -172,7 +194,8 @@ impl DynamicEntry {
         let mut strategy = PeekableStrategy::new(strategy);
 
         for (_, week_dates) in month.year().iter_weeks_in(month.month()) {
-            let schedule = WorkSchedule::new(*week_dates.start(), *week_dates.end());
+            let schedule = WorkSchedule::new(*week_dates.start(), *week_dates.end())
+                .with_chunking(options.minimum_chunk, options.rounding);
 
             let scheduled_tasks = schedule.schedule(&mut strategy, &mut scheduler, |date| {
                 month.working_time_on_day(date)
@@ -227,6 +250,8 @@ mod tests {
                     input: DynamicEntryInput::Flex { flex: 1 },
                     pause: None,
                     start: None,
+                    min_chunk: None,
+                    rounding: None,
                     skip_dates: ArrayVec::new(),
                 }]
             })
@@ -248,6 +273,8 @@ mod tests {
                     },
                     pause: None,
                     start: None,
+                    min_chunk: None,
+                    rounding: None,
                     skip_dates: ArrayVec::new(),
                 }]
             }),
@@ -55,6 +55,8 @@ pub struct Entry {
     end: TimeStamp,
     pause: Option<WorkingDuration>,
     is_vacation: Option<bool>,
+    #[serde(default)]
+    is_sick: bool,
     /// Can be used to introduce randomness around the specified date.
     ///
     /// For example if a `flex` of `"00:30"` is specified, then this program
@@ -78,6 +80,7 @@ impl Entry {
             end: span.end(),
             pause,
             is_vacation,
+            is_sick: false,
             flex: WorkingDuration::default(),
         }
     }
@@ -106,10 +109,29 @@ impl Entry {
         self.is_vacation.unwrap_or(false)
     }
 
+    pub fn is_sick(&self) -> bool {
+        self.is_sick
+    }
+
     // TODO: make use of the flex
     pub fn flex(&self) -> WorkingDuration {
         self.flex
     }
+
+    /// Checks that the entry does not end before it starts.
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.start > self.end {
+            return Err(anyhow::anyhow!(
+                "the entry `{}` on day {} ends at {}, which is before its start at {}",
+                self.action,
+                self.day(),
+                self.end,
+                self.start
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl<'de> MapEntry<'de> for Entry {
@@ -1,7 +1,7 @@
 use serde::Deserialize;
 
-use crate::input::scheduler::Strategy;
-use crate::time::{Date, Month, Year};
+use crate::input::scheduler::{Strategy, UnscheduledPolicy};
+use crate::time::{Date, Month, TimeSpan, Year};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct General {
@@ -10,7 +10,12 @@ pub struct General {
     department: String,
     signature: Option<GeneralSignature>,
     #[serde(default)]
-    strategy: Strategy,
+    strategy: Option<Strategy>,
+    /// Deprecated, use `working_hours` in the `[scheduler]` section instead.
+    #[serde(default)]
+    working_hours: Option<TimeSpan>,
+    #[serde(default)]
+    unscheduled: UnscheduledPolicy,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -41,7 +46,15 @@ impl General {
         &self.department
     }
 
-    pub const fn strategy(&self) -> Strategy {
+    pub const fn strategy(&self) -> Option<Strategy> {
         self.strategy
     }
+
+    pub const fn working_hours(&self) -> Option<TimeSpan> {
+        self.working_hours
+    }
+
+    pub const fn unscheduled(&self) -> UnscheduledPolicy {
+        self.unscheduled
+    }
 }
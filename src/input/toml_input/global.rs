@@ -1,12 +1,26 @@
+use std::fs::File;
 use std::path::{Path, PathBuf};
 
+use anyhow::Context;
 use formatx::Template;
-use serde::Deserialize;
+use serde::{de, Deserialize};
 
 use crate::input::toml_input::{self, About, Contract, DynamicEntry, Entry, Mail, RepeatingEvent};
+use crate::time::holiday::{Closure, HolidayCalendar, State};
 use crate::time::{Date, Month, Year};
 use crate::utils::{self, StrExt};
 
+/// Decides how the latex source of the time sheet is generated.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Generator {
+    /// The latex file is generated by this program.
+    #[default]
+    Native,
+    /// The latex file is generated by the embedded TimeSheetGenerator (requires java).
+    Jar,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     #[serde(default)]
@@ -15,6 +29,23 @@ pub struct Config {
     output_format: Option<String>,
     #[serde(default)]
     preserve_dir: Option<PathBuf>,
+    #[serde(default)]
+    generator: Generator,
+    #[serde(default)]
+    holidays: State,
+}
+
+fn deserialize_repeating<'de, D>(deserializer: D) -> Result<Vec<RepeatingEvent>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let events: Vec<RepeatingEvent> = utils::deserialize_map_entry(deserializer)?;
+
+    for event in &events {
+        event.check().map_err(de::Error::custom)?;
+    }
+
+    Ok(events)
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -24,11 +55,21 @@ pub struct Global {
     mail: Option<Mail>,
     #[serde(deserialize_with = "utils::deserialize_map_entry")]
     contract: Vec<Contract>,
-    #[serde(default, deserialize_with = "utils::deserialize_map_entry")]
+    #[serde(default, deserialize_with = "deserialize_repeating")]
     repeating: Vec<RepeatingEvent>,
+    #[serde(default)]
+    closure: Vec<Closure>,
 }
 
 impl Global {
+    /// Reads the global file the same way as the month files are read.
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+
+        utils::toml_from_reader(File::open(path)?)
+            .with_context(|| format!("failed to parse `{}`", path.display()))
+    }
+
     #[must_use]
     pub fn about(&self) -> &About {
         &self.about
@@ -55,11 +96,46 @@ impl Global {
             .and_then(|config| config.preserve_dir.as_deref())
     }
 
+    #[must_use]
+    pub fn generator(&self) -> Generator {
+        self.config
+            .as_ref()
+            .map_or_else(Generator::default, |config| config.generator)
+    }
+
+    /// The public holidays of the state in which one works and the closures
+    /// of the institution.
+    #[must_use]
+    pub fn holidays(&self) -> HolidayCalendar {
+        let state = self
+            .config
+            .as_ref()
+            .map_or_else(State::default, |config| config.holidays);
+
+        HolidayCalendar::new(state).with_closures(self.closure.clone())
+    }
+
+    /// Returns the overrides of the repeating events in the month, that do not
+    /// match an occurrence, together with the action of the event.
+    pub fn unmatched_overrides<'a>(
+        &'a self,
+        year: Year,
+        month: Month,
+        can_repeat_on: impl Copy + Fn(Date) -> bool + 'a,
+        department: &'a str,
+    ) -> impl Iterator<Item = (&'a str, Date)> + 'a {
+        self.repeating.iter().flat_map(move |event| {
+            event
+                .unmatched_overrides(year, month, department, can_repeat_on)
+                .map(|date| (event.action(), date))
+        })
+    }
+
     pub fn repeating_in_month<'a>(
         &'a self,
         year: Year,
         month: Month,
-        mut can_repeat_on: impl FnMut(Date) -> bool + 'a,
+        can_repeat_on: impl Copy + Fn(Date) -> bool + 'a,
         department: &'a str,
     ) -> impl Iterator<Item = Entry> + 'a {
         (Date::first_day(year, month)..=Date::last_day(year, month))
@@ -85,7 +161,7 @@ impl Global {
     }
 
     #[must_use]
-    pub fn resolve_output(&self, month: &toml_input::Month) -> String {
+    pub fn resolve_output(&self, month: &toml_input::Month, extension: &str) -> String {
         let format = self
             .config
             .as_ref()
@@ -121,11 +197,11 @@ impl Global {
                 },
             );
 
-        if format.ends_with(".pdf") {
-            format
-        } else {
-            format!("{}.pdf", format)
-        }
+        // the output format might have been specified with the pdf extension,
+        // which should be replaced if the sheet is rendered in a different format:
+        let format = format.strip_suffix(".pdf").unwrap_or(&format);
+
+        format!("{}.{}", format, extension)
     }
 
     #[must_use]
@@ -1,8 +1,11 @@
+use serde::de;
 use serde::Deserialize;
 
 use crate::input::json_input::Entry;
+use crate::input::toml_input::AbsenceKey;
 use crate::input::Task;
 use crate::time::{Date, Month, TimeSpan, TimeStamp, WorkingDuration, Year};
+use crate::utils::{self, MapEntry};
 use crate::working_duration;
 
 const fn default_months() -> usize {
@@ -19,11 +22,34 @@ pub struct Holiday {
     implicit: bool,
     #[serde(default)]
     start: Option<TimeStamp>,
+    #[serde(default)]
     day: usize,
+    /// The days of a vacation block like `[holiday.24-28]`.
+    #[serde(skip)]
+    days: Option<AbsenceKey>,
     #[serde(default = "default_months")]
     months: usize,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum HolidayInput {
+    Blocks(#[serde(deserialize_with = "utils::deserialize_map_entry")] Vec<Holiday>),
+    Single(Holiday),
+}
+
+/// Deserializes either a single `[holiday]` table or multiple vacation blocks
+/// like `[holiday.24-28]`.
+pub(crate) fn deserialize_holidays<'de, D>(deserializer: D) -> Result<Vec<Holiday>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    Ok(match HolidayInput::deserialize(deserializer)? {
+        HolidayInput::Blocks(blocks) => blocks,
+        HolidayInput::Single(holiday) => vec![holiday],
+    })
+}
+
 const fn divide_and_round(dividend: usize, divisor: usize) -> usize {
     (dividend + (divisor / 2)) / divisor
 }
@@ -33,8 +59,9 @@ impl Holiday {
     const HOLIDAYS_PER_YEAR: usize = 20;
     const SCALAR_IN_MINS: usize = 237; // 3.95h * 60min
 
+    /// Returns how much vacation one can take for the given number of months.
     #[must_use]
-    const fn duration(monthly_time: WorkingDuration, months: usize) -> WorkingDuration {
+    pub(crate) const fn duration(monthly_time: WorkingDuration, months: usize) -> WorkingDuration {
         let mins_per_month = monthly_time.as_mins() as usize;
 
         let dividend = mins_per_month * Self::HOLIDAYS_PER_YEAR * Self::SCALAR_IN_MINS * months;
@@ -43,32 +70,69 @@ impl Holiday {
         WorkingDuration::from_mins(divide_and_round(dividend, divisor) as u16)
     }
 
+    /// Returns how long a single day of vacation is.
+    #[must_use]
+    pub(crate) const fn day_duration(monthly_time: WorkingDuration) -> WorkingDuration {
+        let year = Self::duration(monthly_time, 12).as_mins() as usize;
+
+        WorkingDuration::from_mins(divide_and_round(year, Self::HOLIDAYS_PER_YEAR) as u16)
+    }
+
     #[must_use]
     pub fn is_implicit(&self) -> bool {
         self.implicit
     }
 
+    /// The first day of the holiday.
     #[must_use]
     pub fn day(&self) -> usize {
-        self.day
+        match self.days {
+            Some(AbsenceKey::Day(day)) => day,
+            Some(AbsenceKey::Range { start, .. }) => start,
+            None => self.day,
+        }
     }
 
+    /// The last day of the holiday.
     #[must_use]
-    pub fn to_entry(
+    pub fn last_day(&self) -> usize {
+        match self.days {
+            Some(AbsenceKey::Range { end, .. }) => end,
+            _ => self.day(),
+        }
+    }
+
+    /// Whether the holiday is a block of vacation days, on which nothing
+    /// else should be scheduled.
+    #[must_use]
+    pub fn is_block(&self) -> bool {
+        self.days.is_some()
+    }
+
+    /// For how many months the holiday is taken.
+    #[must_use]
+    pub fn months(&self) -> usize {
+        self.months
+    }
+
+    /// Makes the vacation entries of the holiday.
+    ///
+    /// The vacation of a block is spread evenly across its workdays, with one
+    /// entry per day, while a single holiday can be split up by `schedule`.
+    pub fn to_entries(
         &self,
         year: Year,
         month: Month,
         monthly_time: WorkingDuration,
+        is_workday: impl Fn(Date) -> bool,
         mut schedule: impl FnMut(Task) -> Vec<(Date, TimeSpan)>,
-    ) -> Vec<Entry> {
+    ) -> anyhow::Result<Vec<Entry>> {
         if !self.implicit {
-            return vec![];
+            return Ok(vec![]);
         }
 
         let duration = Self::duration(monthly_time, self.months);
-
-        let date = Date::new(year, month, self.day).expect("invalid day for month");
-        schedule({
+        let make_task = |duration, date| {
             let mut task = Task::new_duration(duration).with_suggested_date(date);
 
             if let Some(start) = self.start {
@@ -76,10 +140,59 @@ impl Holiday {
             }
 
             task
-        })
-        .into_iter()
-        .map(|(date, span)| Entry::new_vacation("Urlaub", date.day(), span.start(), span.end()))
-        .collect()
+        };
+
+        let mut scheduled = Vec::new();
+        if self.is_block() {
+            let mut dates = Vec::new();
+            for day in self.day()..=self.last_day() {
+                let date = Date::new(year, month, day)?;
+                if is_workday(date) {
+                    dates.push(date);
+                }
+            }
+
+            if dates.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "the holiday from {} to {} has no workdays",
+                    self.day(),
+                    self.last_day()
+                ));
+            }
+
+            let count = dates.len() as u16;
+            let per_day = duration.as_mins() / count;
+            let remainder = duration.as_mins() % count;
+            for (i, date) in dates.into_iter().enumerate() {
+                // the remaining minutes are put on the first days
+                let extra = u16::from((i as u16) < remainder);
+                let part = WorkingDuration::from_mins(per_day + extra);
+                let spans = schedule(make_task(part, date).with_can_be_split(false));
+
+                if spans.is_empty() {
+                    return Ok(vec![]);
+                }
+
+                scheduled.extend(spans);
+            }
+        } else {
+            scheduled = schedule(make_task(duration, Date::new(year, month, self.day)?));
+        }
+
+        Ok(scheduled
+            .into_iter()
+            .map(|(date, span)| Entry::new_vacation("Urlaub", date.day(), span.start(), span.end()))
+            .collect())
+    }
+}
+
+impl<'de> MapEntry<'de> for Holiday {
+    type Key = AbsenceKey;
+    type Value = Self;
+
+    fn new(key: Self::Key, mut value: Self::Value) -> Self {
+        value.days = Some(key);
+        value
     }
 }
 
@@ -97,6 +210,35 @@ mod tests {
         );
     }
 
+    #[derive(Debug, Deserialize)]
+    struct Input {
+        #[serde(deserialize_with = "deserialize_holidays")]
+        holiday: Vec<Holiday>,
+    }
+
+    #[test]
+    fn test_deserialize_holidays() {
+        let input: Input = toml::from_str("[holiday]\nday = 23\nmonths = 2\n").unwrap();
+        assert_eq!(input.holiday.len(), 1);
+        assert_eq!(input.holiday[0].day(), 23);
+        assert_eq!(input.holiday[0].last_day(), 23);
+        assert_eq!(input.holiday[0].months(), 2);
+        assert!(!input.holiday[0].is_block());
+
+        let input: Input =
+            toml::from_str("[holiday.24-28]\nstart = \"10:00\"\n\n[holiday.3]\n").unwrap();
+        assert_eq!(input.holiday.len(), 2);
+        assert_eq!(
+            (input.holiday[0].day(), input.holiday[0].last_day()),
+            (24, 28)
+        );
+        assert_eq!(
+            (input.holiday[1].day(), input.holiday[1].last_day()),
+            (3, 3)
+        );
+        assert!(input.holiday.iter().all(Holiday::is_block));
+    }
+
     #[test]
     fn test_divide_and_round() {
         for left in 0..=1_000 {
@@ -33,14 +33,18 @@ impl<'a> From<MailAddress<'a>> for Mailbox {
 pub struct Smtp {
     url: String,
     username: String,
-    password: String,
+    /// Can be left out, if the global file should not contain the password.
+    #[serde(default)]
+    password: Option<String>,
     #[serde(default)]
     use_starttls: bool,
 }
 
 impl Smtp {
-    #[must_use]
-    pub fn to_transport(&self) -> SmtpTransport {
+    pub fn to_transport(&self) -> anyhow::Result<SmtpTransport> {
+        let password = self.password.clone().ok_or_else(|| {
+            anyhow::anyhow!("the password of the smtp server is missing in the global file")
+        })?;
         let relay = self.url.as_str();
         let transport = {
             if self.use_starttls {
@@ -51,12 +55,9 @@ impl Smtp {
         }
         .unwrap();
 
-        transport
-            .credentials(Credentials::new(
-                self.username.clone(),
-                self.password.clone(),
-            ))
-            .build()
+        Ok(transport
+            .credentials(Credentials::new(self.username.clone(), password))
+            .build())
     }
 }
 
@@ -72,7 +73,7 @@ impl Mail {
         MessageBuilder::new().from(self.from.clone().into())
     }
 
-    pub fn to_transport(&self) -> SmtpTransport {
+    pub fn to_transport(&self) -> anyhow::Result<SmtpTransport> {
         self.smtp.to_transport()
     }
 }
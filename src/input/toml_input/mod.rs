@@ -10,6 +10,8 @@ mod holiday;
 mod mail;
 mod month;
 mod repeating;
+mod scheduler;
+mod sick;
 mod signature;
 mod transfer;
 
@@ -25,5 +27,7 @@ pub use holiday::*;
 pub use mail::*;
 pub use month::*;
 pub use repeating::*;
+pub use scheduler::*;
+pub use sick::*;
 pub use signature::*;
 pub use transfer::*;
@@ -1,7 +1,12 @@
-use serde::Deserialize;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{de, Deserialize};
 
 use crate::input::toml_input::{
-    Absence, DynamicEntry, Entry, General, Holiday, MultiEntry, Transfer,
+    self, Absence, DynamicEntry, Entry, General, Holiday, MultiEntry, SchedulerSettings, Sick,
+    Transfer,
 };
 use crate::time::Date;
 use crate::utils::{self, MapEntry};
@@ -51,20 +56,151 @@ impl<'de> MapEntry<'de> for EitherEntry {
     }
 }
 
+fn deserialize_entries<'de, D>(deserializer: D) -> Result<Vec<EitherEntry>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let entries: Vec<EitherEntry> = utils::deserialize_map_entry(deserializer)?;
+
+    for entry in entries.iter().flatten() {
+        entry.check().map_err(de::Error::custom)?;
+    }
+
+    Ok(entries)
+}
+
+fn deserialize_absences<'de, D>(deserializer: D) -> Result<Vec<Absence>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let absences: Vec<Absence> = utils::deserialize_map_entry(deserializer)?;
+
+    for absence in &absences {
+        absence.check().map_err(de::Error::custom)?;
+    }
+
+    Ok(absences)
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Month {
     general: General,
     transfer: Option<Transfer>,
-    holiday: Option<Holiday>,
-    #[serde(default, deserialize_with = "utils::deserialize_map_entry")]
+    #[serde(default, deserialize_with = "toml_input::deserialize_holidays")]
+    holiday: Vec<Holiday>,
+    #[serde(default)]
+    scheduler: SchedulerSettings,
+    #[serde(default, deserialize_with = "deserialize_entries")]
     entries: Vec<EitherEntry>,
     #[serde(default, deserialize_with = "utils::deserialize_map_entry")]
     dynamic: Vec<DynamicEntry>,
-    #[serde(default, deserialize_with = "utils::deserialize_map_entry")]
+    #[serde(default, deserialize_with = "deserialize_absences")]
     absence: Vec<Absence>,
+    #[serde(default, deserialize_with = "utils::deserialize_map_entry")]
+    sick: Vec<Sick>,
 }
 
 impl Month {
+    /// Reads the month files that [`Month::find_files`] finds in the workspace,
+    /// sorted by their path.
+    ///
+    /// Fails if one of them is not a valid month file.
+    pub fn from_directory(
+        workspace: impl AsRef<Path>,
+        global: impl AsRef<Path>,
+    ) -> anyhow::Result<Vec<(PathBuf, Self)>> {
+        Self::find_files(workspace, global)?
+            .into_iter()
+            .map(|path| {
+                let month = utils::toml_from_reader(File::open(&path)?)
+                    .with_context(|| format!("failed to parse `{}`", path.display()))?;
+
+                Ok((path, month))
+            })
+            .collect()
+    }
+
+    /// Finds the month files in a workspace, which can be a directory that is
+    /// searched recursively or a glob pattern like `2022/*.toml`.
+    ///
+    /// The global file, hidden directories and toml files without a `[general]`
+    /// section are skipped. The files are not parsed as months and files with
+    /// invalid toml are kept, so that a broken month is reported when it is made.
+    pub fn find_files(
+        workspace: impl AsRef<Path>,
+        global: impl AsRef<Path>,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let workspace = workspace.as_ref();
+        let global = dunce::canonicalize(global.as_ref()).ok();
+
+        let (base, pattern) = if workspace.is_dir() {
+            (workspace.to_path_buf(), "**/*.toml".to_string())
+        } else {
+            let mut base = PathBuf::new();
+            let mut pattern = Vec::new();
+            for component in workspace.iter() {
+                let component = component.to_string_lossy();
+                if pattern.is_empty() && !component.contains(['*', '?']) {
+                    base.push(component.as_ref());
+                } else {
+                    pattern.push(component.into_owned());
+                }
+            }
+
+            if base.as_os_str().is_empty() {
+                base.push(".");
+            }
+
+            (base, pattern.join("/"))
+        };
+
+        let mut result = Vec::new();
+        let mut directories = vec![base.clone()];
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(&directory)? {
+                let path = entry?.path();
+                let is_hidden = path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+
+                if path.is_dir() {
+                    if !is_hidden {
+                        directories.push(path);
+                    }
+                    continue;
+                }
+
+                let relative = path
+                    .strip_prefix(&base)?
+                    .iter()
+                    .map(|component| component.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                if !utils::matches_glob(&pattern, &relative)
+                    || path.extension().is_none_or(|extension| extension != "toml")
+                    || (global.is_some() && dunce::canonicalize(&path).ok() == global)
+                {
+                    continue;
+                }
+
+                // files that are not valid toml are kept, so the error is reported
+                let is_month = utils::read_to_string(&path)
+                    .ok()
+                    .and_then(|content| content.parse::<toml::Table>().ok())
+                    .is_none_or(|table| table.contains_key("general"));
+
+                if is_month {
+                    result.push(path);
+                }
+            }
+        }
+
+        result.sort();
+
+        Ok(result)
+    }
+
     pub fn general(&self) -> &General {
         &self.general
     }
@@ -73,6 +209,10 @@ impl Month {
         self.transfer
     }
 
+    pub fn set_transfer(&mut self, transfer: Transfer) {
+        self.transfer = Some(transfer);
+    }
+
     pub fn add_entries(&mut self, entries: impl IntoIterator<Item = Entry>) {
         self.entries
             .extend(entries.into_iter().map(EitherEntry::Entry));
@@ -96,7 +236,21 @@ impl Month {
             .flat_map(|absence| absence.to_date_absences(|d| self.make_date(d)))
     }
 
-    pub fn holiday(&self) -> Option<&Holiday> {
-        self.holiday.as_ref()
+    pub fn sick_leave(&self) -> impl Iterator<Item = &Sick> + '_ {
+        self.sick.iter()
+    }
+
+    pub fn holidays(&self) -> impl Iterator<Item = &Holiday> + '_ {
+        self.holiday.iter()
+    }
+
+    /// Returns the scheduler settings, that override the ones of the contract.
+    pub fn scheduler(&self) -> SchedulerSettings {
+        // the strategy and the deprecated working hours can also be
+        // specified in the general section
+        self.scheduler
+            .clone()
+            .or_strategy(self.general.strategy())
+            .or_working_hours(self.general.working_hours())
     }
 }
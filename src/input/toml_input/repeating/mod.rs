@@ -2,8 +2,10 @@ mod custom_end;
 mod repeat_span;
 mod repeating_event;
 mod repeats_every;
+mod rrule;
 
 pub use custom_end::*;
 pub use repeat_span::*;
 pub use repeating_event::*;
 pub use repeats_every::*;
+pub use rrule::*;
@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
-use crate::input::toml_input::repeating::{CustomEnd, RepeatsEvery};
+use crate::input::toml_input::repeating::{CustomEnd, RRule, RepeatsEvery};
 use crate::input::toml_input::{DynamicEntry, Entry};
 use crate::time::{Date, Month, TimeSpan, TimeStamp, WeekDay, WorkingDuration, Year};
 use crate::utils::{ArrayVec, MapEntry};
@@ -34,6 +36,13 @@ impl CustomRepeatInterval {
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(untagged)]
 enum RepeatingKind {
+    /// Repeats according to the recurrence rule, starting on the given date.
+    Rule {
+        rrule: RRule,
+        start_date: Date,
+        #[serde(default)]
+        end_date: Option<Date>,
+    },
     /// Repeats on the given weekdays.
     WeekDays {
         repeats_on: Vec<WeekDay>,
@@ -55,12 +64,13 @@ impl RepeatingKind {
         match self {
             Self::WeekDays { repeats_on, .. } => repeats_on.clone().into_iter(),
             Self::FixedStart { start_date, .. } => vec![start_date.week_day()].into_iter(),
-            Self::FixedDates { .. } => vec![].into_iter(),
+            Self::Rule { .. } | Self::FixedDates { .. } => vec![].into_iter(),
         }
     }
 
     const fn end_date(&self) -> Option<Date> {
         match self {
+            Self::Rule { end_date, .. } => *end_date,
             Self::WeekDays { end_date, .. } => *end_date,
             Self::FixedStart { end_date, .. } => *end_date,
             Self::FixedDates { .. } => None,
@@ -85,15 +95,34 @@ enum EventKind {
     },
 }
 
+/// Changes a single occurrence of a repeating event, for example when
+/// it has been moved by a few hours.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct EventOverride {
+    #[serde(default)]
+    action: Option<String>,
+    #[serde(default)]
+    start: Option<TimeStamp>,
+    #[serde(default)]
+    end: Option<TimeStamp>,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct RepeatingEvent {
-    repeats_every: RepeatsEvery,
+    /// Required, unless the event has an `rrule`, which has its own interval.
+    #[serde(default)]
+    repeats_every: Option<RepeatsEvery>,
     #[serde(flatten)]
     repeating_kind: RepeatingKind,
     #[serde(default)]
     department: Option<String>,
     #[serde(default, rename = "vacation")]
     is_vacation: bool,
+    /// Dates on which the event does not take place.
+    #[serde(default)]
+    except: Vec<Date>,
+    #[serde(default)]
+    overrides: BTreeMap<Date, EventOverride>,
     #[serde(flatten)]
     event_kind: EventKind,
 }
@@ -102,7 +131,8 @@ impl RepeatingEvent {
     fn start(&self) -> Option<Date> {
         match &self.repeating_kind {
             RepeatingKind::WeekDays { .. } => None,
-            RepeatingKind::FixedStart { start_date, .. } => Some(*start_date),
+            RepeatingKind::Rule { start_date, .. }
+            | RepeatingKind::FixedStart { start_date, .. } => Some(*start_date),
             RepeatingKind::FixedDates { dates, .. } => dates.iter().min().copied(),
         }
     }
@@ -123,17 +153,127 @@ impl RepeatingEvent {
             return dates.contains(&date);
         }
 
+        if let RepeatingKind::Rule {
+            rrule,
+            start_date,
+            end_date,
+        } = &self.repeating_kind
+        {
+            return rrule.occurs_on(*start_date, date) && end_date.is_none_or(|end| date <= end);
+        }
+
+        let Some(repeats_every) = self.repeats_every else {
+            return false;
+        };
+
         CustomRepeatInterval::new(
-            self.repeats_every,
+            repeats_every,
             self.custom_end(),
             self.repeating_kind.iter_week_days().collect(),
         )
         .repeats_on(date)
     }
 
+    /// Checks that `repeats_every` is only set for events without an `rrule`,
+    /// that the event does not end before it starts and that the overrides are
+    /// valid.
+    ///
+    /// Dynamic events have no time that could be overridden, so they can not
+    /// have overrides.
+    pub fn check(&self) -> anyhow::Result<()> {
+        match &self.event_kind {
+            EventKind::Dynamic { .. } => {
+                if let Some(date) = self.overrides.keys().next() {
+                    return Err(anyhow::anyhow!(
+                        "the repeating event `{}` is dynamic and can not be overridden on {}, use `except` instead",
+                        self.action(),
+                        date
+                    ));
+                }
+            }
+            EventKind::Normal { start, end, .. } => {
+                if start > end {
+                    return Err(anyhow::anyhow!(
+                        "the repeating event `{}` ends at {}, which is before its start at {}",
+                        self.action(),
+                        end,
+                        start
+                    ));
+                }
+
+                for (date, changes) in &self.overrides {
+                    let start = changes.start.unwrap_or(*start);
+                    let end = changes.end.unwrap_or(*end);
+                    if start >= end {
+                        return Err(anyhow::anyhow!(
+                            "the override of `{}` on {} ends at {}, which is not after its start at {}",
+                            self.action(),
+                            date,
+                            end,
+                            start
+                        ));
+                    }
+                }
+            }
+        }
+
+        let has_rule = matches!(self.repeating_kind, RepeatingKind::Rule { .. });
+
+        match (has_rule, self.repeats_every) {
+            (true, Some(_)) => Err(anyhow::anyhow!(
+                "the repeating event `{}` can not have both `rrule` and `repeats_every`, use INTERVAL in the rule instead",
+                self.action()
+            )),
+            (false, None) => Err(anyhow::anyhow!(
+                "the repeating event `{}` is missing `repeats_every`",
+                self.action()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks if the event takes place on the date, which is not the case
+    /// for the dates in `except`.
+    #[must_use]
+    fn occurs_on(&self, date: Date) -> bool {
+        !self.except.contains(&date) && self.repeats_on(date)
+    }
+
+    #[must_use]
+    fn applies_to(&self, department: &str) -> bool {
+        self.department.is_none() || self.department.as_deref() == Some(department)
+    }
+
+    /// The action of the event.
+    #[must_use]
+    pub fn action(&self) -> &str {
+        match &self.event_kind {
+            EventKind::Dynamic { entry } => entry.action(),
+            EventKind::Normal { action, .. } => action,
+        }
+    }
+
+    /// Returns the dates in the month of the overrides, on which the event
+    /// does not take place.
+    pub fn unmatched_overrides<'a>(
+        &'a self,
+        year: Year,
+        month: Month,
+        department: &'a str,
+        can_repeat_on: impl Fn(Date) -> bool + 'a,
+    ) -> impl Iterator<Item = Date> + 'a {
+        self.overrides
+            .keys()
+            .copied()
+            .filter(move |date| {
+                date.year() == year && date.month() == month && self.applies_to(department)
+            })
+            .filter(move |date| !(can_repeat_on(*date) && self.occurs_on(*date)))
+    }
+
     fn to_dynamic_entry(&self, department: &str) -> Option<DynamicEntry> {
         // If a department is specified, only apply if the department matches
-        if self.department.is_some() && self.department.as_deref() != Some(department) {
+        if !self.applies_to(department) {
             return None;
         }
 
@@ -158,7 +298,7 @@ impl RepeatingEvent {
 
         if let Some(e) = self.to_dynamic_entry(department) {
             for date in range.clone() {
-                if can_repeat_on(date) && self.repeats_on(date) {
+                if can_repeat_on(date) && self.occurs_on(date) {
                     entries.push(
                         e.clone()
                             .with_skip_dates(range.clone().filter(|d| d != &date).collect()),
@@ -171,12 +311,12 @@ impl RepeatingEvent {
     }
 
     pub fn to_entry(&self, date: Date, department: &str) -> Option<Entry> {
-        if !self.repeats_on(date) {
+        if !self.occurs_on(date) {
             return None;
         }
 
         // If a department is specified, only apply if the department matches
-        if self.department.is_some() && self.department.as_deref() != Some(department) {
+        if !self.applies_to(department) {
             return None;
         }
 
@@ -187,10 +327,17 @@ impl RepeatingEvent {
             pause,
         } = &self.event_kind
         {
+            let changes = self.overrides.get(&date);
+
             Some(Entry::new(
                 date.day(),
-                action.to_string(),
-                TimeSpan::new(*start, *end),
+                changes
+                    .and_then(|changes| changes.action.clone())
+                    .unwrap_or_else(|| action.to_string()),
+                TimeSpan::new(
+                    changes.and_then(|changes| changes.start).unwrap_or(*start),
+                    changes.and_then(|changes| changes.end).unwrap_or(*end),
+                ),
                 *pause,
                 Some(self.is_vacation),
             ))
@@ -19,6 +19,17 @@ impl RepeatsEvery {
         Self { n, span }
     }
 
+    /// After how many spans the event repeats.
+    #[must_use]
+    pub const fn interval(&self) -> usize {
+        self.n
+    }
+
+    #[must_use]
+    pub const fn span(&self) -> RepeatSpan {
+        self.span
+    }
+
     /// Returns how often an event has occured between `start` and `date`.
     ///
     /// If an event is on `date`, it is not counted.
@@ -32,6 +43,12 @@ impl RepeatsEvery {
     }
 }
 
+impl Default for RepeatsEvery {
+    fn default() -> Self {
+        Self::new(1, RepeatSpan::Week)
+    }
+}
+
 impl FromStr for RepeatsEvery {
     type Err = anyhow::Error;
 
@@ -0,0 +1,535 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use crate::input::toml_input::repeating::{RepeatSpan, RepeatsEvery};
+use crate::time::{Date, Month, WeekDay, Year};
+
+/// A recurrence rule like it is defined in [RFC 5545](https://www.rfc-editor.org/rfc/rfc5545#section-3.3.10).
+///
+/// For example `FREQ=MONTHLY;BYDAY=1MO` repeats on the first monday of each month
+/// and `FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1` on the last workday.
+///
+/// The supported parts are `FREQ`, `INTERVAL`, `COUNT`, `UNTIL`, `BYDAY`,
+/// `BYMONTHDAY`, `BYMONTH`, `BYSETPOS` and `WKST` (only `MO`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct RRule {
+    repeats_every: RepeatsEvery,
+    until: Option<Date>,
+    count: Option<usize>,
+    by_day: Vec<(Option<isize>, WeekDay)>,
+    by_month_day: Vec<isize>,
+    by_month: Vec<Month>,
+    by_set_pos: Vec<isize>,
+}
+
+#[must_use]
+fn week_start(date: Date) -> Date {
+    date - WeekDay::Monday.days_until(date.week_day())
+}
+
+#[must_use]
+fn first_day_of_year(year: Year) -> Date {
+    Date::first_day(year, Month::new(1))
+}
+
+#[must_use]
+fn last_day_of_year(year: Year) -> Date {
+    Date::last_day(year, Month::new(12))
+}
+
+/// Checks if the `position` (starting at 1) of an element in a list with
+/// `len` elements is the `n`th, where a negative `n` counts from the end.
+#[must_use]
+const fn is_nth(n: isize, position: usize, len: usize) -> bool {
+    if n > 0 {
+        position == n.unsigned_abs()
+    } else {
+        len + 1 - position == n.unsigned_abs()
+    }
+}
+
+fn parse_number<T: FromStr>(key: &str, value: &str) -> anyhow::Result<T> {
+    value
+        .trim_start_matches('+')
+        .parse::<T>()
+        .map_err(|_| anyhow::anyhow!("invalid value for {}: {}", key, value))
+}
+
+fn parse_list<T>(
+    key: &str,
+    value: &str,
+    range: RangeInclusive<usize>,
+    f: impl Fn(isize) -> T,
+) -> anyhow::Result<Vec<T>> {
+    value
+        .split(',')
+        .map(|part| {
+            let number = parse_number::<isize>(key, part)?;
+
+            if !range.contains(&number.unsigned_abs()) {
+                return Err(anyhow::anyhow!("invalid value for {}: {}", key, part));
+            }
+
+            Ok(f(number))
+        })
+        .collect()
+}
+
+fn parse_week_day(value: &str) -> anyhow::Result<WeekDay> {
+    match value {
+        "MO" => Ok(WeekDay::Monday),
+        "TU" => Ok(WeekDay::Tuesday),
+        "WE" => Ok(WeekDay::Wednesday),
+        "TH" => Ok(WeekDay::Thursday),
+        "FR" => Ok(WeekDay::Friday),
+        "SA" => Ok(WeekDay::Saturday),
+        "SU" => Ok(WeekDay::Sunday),
+        _ => Err(anyhow::anyhow!("Invalid week day: {}", value)),
+    }
+}
+
+fn parse_by_day(value: &str) -> anyhow::Result<Vec<(Option<isize>, WeekDay)>> {
+    value
+        .split(',')
+        .map(|part| {
+            let split = part.len().saturating_sub(2);
+            if !part.is_char_boundary(split) {
+                return Err(anyhow::anyhow!("invalid value for BYDAY: {}", part));
+            }
+
+            let (ordinal, week_day) = part.split_at(split);
+            let week_day = parse_week_day(week_day)?;
+
+            if ordinal.is_empty() {
+                return Ok((None, week_day));
+            }
+
+            let ordinal = parse_number::<isize>("BYDAY", ordinal)?;
+            if ordinal == 0 || ordinal.unsigned_abs() > 53 {
+                return Err(anyhow::anyhow!("invalid value for BYDAY: {}", part));
+            }
+
+            Ok((Some(ordinal), week_day))
+        })
+        .collect()
+}
+
+/// Parses a date like `20221231` or `20221231T235959Z`, the time is ignored.
+fn parse_date(value: &str) -> anyhow::Result<Date> {
+    let invalid = || anyhow::anyhow!("invalid value for UNTIL: {}", value);
+
+    if value.len() < 8 || !value.is_char_boundary(8) {
+        return Err(invalid());
+    }
+
+    let (year, rest) = value[..8].split_at(4);
+    let (month, day) = rest.split_at(2);
+
+    let month = Month::try_from(parse_number::<usize>("UNTIL", month)?).map_err(|_| invalid())?;
+    Date::new(
+        Year::new(parse_number("UNTIL", year)?),
+        month,
+        parse_number("UNTIL", day)?,
+    )
+    .map_err(|_| invalid())
+}
+
+impl RRule {
+    /// Returns the days of the period (like the week or month) in which the date is.
+    #[must_use]
+    fn period_of(&self, date: Date) -> RangeInclusive<Date> {
+        match self.repeats_every.span() {
+            RepeatSpan::Day => date..=date,
+            RepeatSpan::Week => week_start(date)..=(week_start(date) + 6),
+            RepeatSpan::Month => {
+                Date::first_day(date.year(), date.month())
+                    ..=Date::last_day(date.year(), date.month())
+            }
+            RepeatSpan::Year => first_day_of_year(date.year())..=last_day_of_year(date.year()),
+        }
+    }
+
+    /// Returns how many periods are between the period of `start` and the one of `date`.
+    #[must_use]
+    fn periods_between(&self, start: Date, date: Date) -> usize {
+        let years = date.year().as_usize() - start.year().as_usize();
+
+        match self.repeats_every.span() {
+            RepeatSpan::Day => start.days_until(date),
+            RepeatSpan::Week => week_start(start).days_until(week_start(date)) / 7,
+            RepeatSpan::Month => years * 12 + date.month().as_usize() - start.month().as_usize(),
+            RepeatSpan::Year => years,
+        }
+    }
+
+    #[must_use]
+    fn matches_by_day(&self, date: Date) -> bool {
+        // the ordinal is relative to the month, except for yearly rules without BYMONTH
+        let (position, len) = {
+            if self.repeats_every.span() == RepeatSpan::Year && self.by_month.is_empty() {
+                let year = date.year();
+                (
+                    year.cumulative_days()[date.month().as_usize() - 1] + date.day(),
+                    year.days(),
+                )
+            } else {
+                (
+                    date.day(),
+                    date.year().number_of_days_in_month(date.month()),
+                )
+            }
+        };
+
+        self.by_day.iter().any(|(ordinal, week_day)| {
+            *week_day == date.week_day()
+                && ordinal.is_none_or(|n| {
+                    if n > 0 {
+                        (position - 1) / 7 + 1 == n.unsigned_abs()
+                    } else {
+                        (len - position) / 7 + 1 == n.unsigned_abs()
+                    }
+                })
+        })
+    }
+
+    #[must_use]
+    fn matches(&self, start: Date, date: Date) -> bool {
+        if !self.by_month.is_empty() && !self.by_month.contains(&date.month()) {
+            return false;
+        }
+
+        let days_in_month = date.year().number_of_days_in_month(date.month());
+        if !self.by_month_day.is_empty()
+            && !self
+                .by_month_day
+                .iter()
+                .any(|n| is_nth(*n, date.day(), days_in_month))
+        {
+            return false;
+        }
+
+        if !self.by_day.is_empty() {
+            return self.matches_by_day(date);
+        }
+
+        if !self.by_month_day.is_empty() {
+            return true;
+        }
+
+        // without any BYDAY or BYMONTHDAY the rule repeats like the start
+        match self.repeats_every.span() {
+            RepeatSpan::Day => true,
+            RepeatSpan::Week => date.week_day() == start.week_day(),
+            RepeatSpan::Month => date.day() == start.day(),
+            RepeatSpan::Year => {
+                date.day() == start.day()
+                    && (!self.by_month.is_empty() || date.month() == start.month())
+            }
+        }
+    }
+
+    /// Returns all occurrences in the period of the date.
+    #[must_use]
+    fn occurrences_in_period_of(&self, start: Date, date: Date) -> Vec<Date> {
+        if date < start
+            || !self
+                .periods_between(start, date)
+                .is_multiple_of(self.repeats_every.interval())
+        {
+            return Vec::new();
+        }
+
+        let period = self.period_of(date);
+        let mut candidates = Vec::new();
+        let mut candidate = *period.start();
+        while candidate <= *period.end() {
+            if self.matches(start, candidate) {
+                candidates.push(candidate);
+            }
+
+            candidate += 1;
+        }
+
+        let len = candidates.len();
+        candidates
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                self.by_set_pos.is_empty() || self.by_set_pos.iter().any(|n| is_nth(*n, i + 1, len))
+            })
+            .map(|(_, candidate)| candidate)
+            .filter(|candidate| {
+                *candidate >= start && self.until.is_none_or(|until| *candidate <= until)
+            })
+            .collect()
+    }
+
+    /// Checks if the rule, which starts on `start`, has an occurrence on the date.
+    #[must_use]
+    pub fn occurs_on(&self, start: Date, date: Date) -> bool {
+        if !self.occurrences_in_period_of(start, date).contains(&date) {
+            return false;
+        }
+
+        let Some(count) = self.count else {
+            return true;
+        };
+
+        // count the occurrences before the date
+        let mut previous = 0;
+        let mut period_start = start;
+        while period_start <= date {
+            previous += self
+                .occurrences_in_period_of(start, period_start)
+                .into_iter()
+                .filter(|occurrence| *occurrence < date)
+                .count();
+
+            period_start = *self.period_of(period_start).end() + 1;
+        }
+
+        previous < count
+    }
+}
+
+impl FromStr for RRule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut span = None;
+        let mut interval = 1;
+        let mut result = Self {
+            repeats_every: RepeatsEvery::default(),
+            until: None,
+            count: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+        };
+
+        for part in s.trim().trim_start_matches("RRULE:").split(';') {
+            let (key, value) = part
+                .split_once('=')
+                .with_context(|| anyhow::anyhow!("invalid part of the rrule: {}", part))?;
+
+            match key.to_uppercase().as_str() {
+                "FREQ" => {
+                    span = Some(match value {
+                        "DAILY" => RepeatSpan::Day,
+                        "WEEKLY" => RepeatSpan::Week,
+                        "MONTHLY" => RepeatSpan::Month,
+                        "YEARLY" => RepeatSpan::Year,
+                        _ => return Err(anyhow::anyhow!("unsupported frequency: {}", value)),
+                    });
+                }
+                "INTERVAL" => {
+                    interval = parse_number(key, value)?;
+                    if interval == 0 {
+                        return Err(anyhow::anyhow!("the interval must be greater than 0"));
+                    }
+                }
+                "COUNT" => result.count = Some(parse_number(key, value)?),
+                "UNTIL" => result.until = Some(parse_date(value)?),
+                "BYDAY" => result.by_day = parse_by_day(value)?,
+                "BYMONTHDAY" => result.by_month_day = parse_list(key, value, 1..=31, |n| n)?,
+                "BYMONTH" => {
+                    result.by_month =
+                        parse_list(key, value, 1..=12, |n| Month::new(n.unsigned_abs()))?;
+                }
+                "BYSETPOS" => result.by_set_pos = parse_list(key, value, 1..=366, |n| n)?,
+                "WKST" if value == "MO" => {}
+                _ => return Err(anyhow::anyhow!("unsupported part of the rrule: {}", part)),
+            }
+        }
+
+        let span = span.with_context(|| anyhow::anyhow!("the rrule `{}` has no FREQ", s))?;
+        result.repeats_every = RepeatsEvery::new(interval, span);
+
+        if result.count.is_some() && result.until.is_some() {
+            return Err(anyhow::anyhow!(
+                "the rrule `{}` can not have both COUNT and UNTIL",
+                s
+            ));
+        }
+
+        Ok(result)
+    }
+}
+
+impl TryFrom<String> for RRule {
+    type Error = <Self as FromStr>::Err;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::date;
+
+    #[track_caller]
+    fn occurrences(rule: &str, start: Date, end: Date) -> Vec<Date> {
+        let rule = rule.parse::<RRule>().expect("rrule should be valid");
+
+        (start..=end)
+            .filter(|date| rule.occurs_on(start, *date))
+            .collect()
+    }
+
+    #[test]
+    fn test_first_monday_of_month() {
+        assert_eq!(
+            occurrences(
+                "FREQ=MONTHLY;BYDAY=1MO",
+                date!(2022:10:01),
+                date!(2023:01:31)
+            ),
+            vec![
+                date!(2022:10:03),
+                date!(2022:11:07),
+                date!(2022:12:05),
+                date!(2023:01:02),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_every_second_thursday() {
+        assert_eq!(
+            occurrences(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=TH",
+                date!(2022:11:03),
+                date!(2022:12:31)
+            ),
+            vec![
+                date!(2022:11:03),
+                date!(2022:11:17),
+                date!(2022:12:01),
+                date!(2022:12:15),
+                date!(2022:12:29),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_workday_of_month() {
+        assert_eq!(
+            occurrences(
+                "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+                date!(2022:07:01),
+                date!(2022:12:31)
+            ),
+            vec![
+                date!(2022:07:29),
+                date!(2022:08:31),
+                date!(2022:09:30),
+                date!(2022:10:31),
+                date!(2022:11:30),
+                date!(2022:12:30),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_friday_and_month_day() {
+        assert_eq!(
+            occurrences(
+                "FREQ=MONTHLY;BYDAY=-1FR",
+                date!(2022:10:01),
+                date!(2022:12:31)
+            ),
+            vec![date!(2022:10:28), date!(2022:11:25), date!(2022:12:30)]
+        );
+
+        assert_eq!(
+            occurrences(
+                "FREQ=MONTHLY;BYMONTHDAY=-1",
+                date!(2023:01:01),
+                date!(2023:03:31)
+            ),
+            vec![date!(2023:01:31), date!(2023:02:28), date!(2023:03:31)]
+        );
+    }
+
+    #[test]
+    fn test_count_and_until() {
+        assert_eq!(
+            occurrences(
+                "FREQ=WEEKLY;BYDAY=TU,TH;COUNT=5",
+                date!(2022:11:01),
+                date!(2022:11:30)
+            ),
+            vec![
+                date!(2022:11:01),
+                date!(2022:11:03),
+                date!(2022:11:08),
+                date!(2022:11:10),
+                date!(2022:11:15),
+            ]
+        );
+
+        assert_eq!(
+            occurrences(
+                "FREQ=DAILY;INTERVAL=3;UNTIL=20221110T235959Z",
+                date!(2022:11:01),
+                date!(2022:11:30)
+            ),
+            vec![
+                date!(2022:11:01),
+                date!(2022:11:04),
+                date!(2022:11:07),
+                date!(2022:11:10),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yearly() {
+        assert_eq!(
+            occurrences(
+                "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH",
+                date!(2022:01:01),
+                date!(2024:12:31)
+            ),
+            vec![date!(2022:11:24), date!(2023:11:23), date!(2024:11:28)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for (rule, error) in [
+            ("BYDAY=MO", "the rrule `BYDAY=MO` has no FREQ"),
+            ("FREQ=HOURLY", "unsupported frequency: HOURLY"),
+            ("FREQ=WEEKLY;BYDAY=XX", "Invalid week day: XX"),
+            ("FREQ=WEEKLY;BYDAY=Ä1", "invalid value for BYDAY: Ä1"),
+            ("FREQ=WEEKLY;BYDAY=1MÖ", "Invalid week day: Ö"),
+            (
+                "FREQ=WEEKLY;INTERVAL=0",
+                "the interval must be greater than 0",
+            ),
+            (
+                "FREQ=MONTHLY;BYMONTHDAY=32",
+                "invalid value for BYMONTHDAY: 32",
+            ),
+            (
+                "FREQ=DAILY;COUNT=2;UNTIL=20221231",
+                "the rrule `FREQ=DAILY;COUNT=2;UNTIL=20221231` can not have both COUNT and UNTIL",
+            ),
+        ] {
+            assert_eq!(
+                rule.parse::<RRule>().unwrap_err().to_string(),
+                error.to_string()
+            );
+        }
+    }
+}
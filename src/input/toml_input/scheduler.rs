@@ -0,0 +1,226 @@
+use serde::Deserialize;
+
+use crate::input::scheduler::{Availability, SchedulerOptions, Strategy};
+use crate::time::{TimeSpan, WorkingDuration};
+use crate::{time_stamp, working_duration};
+
+/// The `[scheduler]` section, which configures how dynamic entries are scheduled.
+///
+/// It can be specified for each contract in the global file and be overridden
+/// in the month file. Values that are not specified keep their default.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct SchedulerSettings {
+    daily_limit: Option<WorkingDuration>,
+    weekly_limit: Option<WorkingDuration>,
+    max_per_day: Option<WorkingDuration>,
+    schedule_with_fixed_entries: Option<bool>,
+    schedule_with_absences: Option<bool>,
+    strategy: Option<Strategy>,
+    working_hours: Option<TimeSpan>,
+    availability: Option<Availability>,
+    min_chunk: Option<WorkingDuration>,
+    rounding: Option<WorkingDuration>,
+}
+
+impl SchedulerSettings {
+    /// The maximum amount of work that is legally allowed on a single day.
+    const LEGAL_DAILY_LIMIT: WorkingDuration = working_duration!(10:00);
+
+    /// Returns the settings, where the specified values of `other` take precedence.
+    #[must_use]
+    pub fn merge(&self, other: &Self) -> Self {
+        Self {
+            daily_limit: other.daily_limit.or(self.daily_limit),
+            weekly_limit: other.weekly_limit.or(self.weekly_limit),
+            max_per_day: other.max_per_day.or(self.max_per_day),
+            schedule_with_fixed_entries: other
+                .schedule_with_fixed_entries
+                .or(self.schedule_with_fixed_entries),
+            schedule_with_absences: other.schedule_with_absences.or(self.schedule_with_absences),
+            strategy: other.strategy.or(self.strategy),
+            working_hours: other.working_hours.or(self.working_hours),
+            availability: other
+                .availability
+                .clone()
+                .or_else(|| self.availability.clone()),
+            min_chunk: other.min_chunk.or(self.min_chunk),
+            rounding: other.rounding.or(self.rounding),
+        }
+    }
+
+    /// Uses the strategy, if no other strategy has been specified.
+    #[must_use]
+    pub fn or_strategy(mut self, strategy: Option<Strategy>) -> Self {
+        self.strategy = self.strategy.or(strategy);
+        self
+    }
+
+    /// Uses the working hours, if no other working hours have been specified.
+    #[must_use]
+    pub fn or_working_hours(mut self, working_hours: Option<TimeSpan>) -> Self {
+        self.working_hours = self.working_hours.or(working_hours);
+        self
+    }
+
+    /// Makes the options for the scheduler and checks that they are valid.
+    pub fn to_options(&self) -> anyhow::Result<SchedulerOptions> {
+        let mut options = SchedulerOptions::default();
+
+        if let Some(daily_limit) = self.daily_limit {
+            options.daily_limit = daily_limit;
+        }
+
+        if let Some(max_per_day) = self.max_per_day {
+            options.maximum_work_per_day = max_per_day;
+        }
+
+        if let Some(should_mix) = self.schedule_with_fixed_entries {
+            options.should_schedule_with_fixed_entries = should_mix;
+        }
+
+        if let Some(should_mix) = self.schedule_with_absences {
+            options.should_schedule_with_absences = should_mix;
+        }
+
+        if let Some(strategy) = self.strategy {
+            options.strategy = strategy;
+        }
+
+        if let Some(working_hours) = self.working_hours {
+            options.working_hours = working_hours;
+        }
+
+        if let Some(min_chunk) = self.min_chunk {
+            options.minimum_chunk = min_chunk;
+        }
+
+        if let Some(rounding) = self.rounding {
+            options.rounding = rounding;
+        }
+
+        options.weekly_limit = self.weekly_limit;
+        options.availability = self.availability.clone();
+
+        if options.daily_limit == WorkingDuration::default() {
+            return Err(anyhow::anyhow!(
+                "the daily limit must be greater than 00:00"
+            ));
+        }
+
+        if options.maximum_work_per_day > Self::LEGAL_DAILY_LIMIT {
+            return Err(anyhow::anyhow!(
+                "the maximum work per day ({}) exceeds the legal limit of {}",
+                options.maximum_work_per_day,
+                Self::LEGAL_DAILY_LIMIT
+            ));
+        }
+
+        if options.daily_limit > options.maximum_work_per_day {
+            return Err(anyhow::anyhow!(
+                "the daily limit ({}) exceeds the maximum work per day ({})",
+                options.daily_limit,
+                options.maximum_work_per_day
+            ));
+        }
+
+        if options.working_hours.start() < time_stamp!(06:00)
+            || options.working_hours.end() > time_stamp!(22:00)
+        {
+            return Err(anyhow::anyhow!(
+                "the working hours ({}) must be between 06:00 and 22:00",
+                options.working_hours
+            ));
+        }
+
+        if options.rounding == WorkingDuration::default() {
+            return Err(anyhow::anyhow!("the rounding must be greater than 00:00"));
+        }
+
+        if options.minimum_chunk > options.daily_limit {
+            return Err(anyhow::anyhow!(
+                "the minimum chunk ({}) exceeds the daily limit ({})",
+                options.minimum_chunk,
+                options.daily_limit
+            ));
+        }
+
+        if let Some(availability) = &options.availability {
+            availability.validate()?;
+        }
+
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_options() {
+        assert_eq!(
+            SchedulerSettings::default().to_options().unwrap(),
+            SchedulerOptions::default()
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let global: SchedulerSettings = toml::from_str(concat!(
+            "daily_limit = \"04:00\"\n",
+            "weekly_limit = \"12:00\"\n",
+            "strategy = \"proportional\"\n",
+        ))
+        .unwrap();
+
+        let month: SchedulerSettings = toml::from_str(concat!(
+            "daily_limit = \"05:00\"\n",
+            "schedule_with_absences = true\n",
+        ))
+        .unwrap();
+
+        let options = global.merge(&month).to_options().unwrap();
+
+        assert_eq!(options.daily_limit, working_duration!(05:00));
+        assert_eq!(options.weekly_limit, Some(working_duration!(12:00)));
+        assert_eq!(options.strategy, Strategy::Proportional);
+        assert!(options.should_schedule_with_absences);
+        assert!(!options.should_schedule_with_fixed_entries);
+    }
+
+    #[test]
+    fn test_invalid_options() {
+        let settings: SchedulerSettings = toml::from_str("daily_limit = \"09:00\"\n").unwrap();
+        assert_eq!(
+            settings.to_options().unwrap_err().to_string(),
+            "the daily limit (09:00) exceeds the maximum work per day (08:00)".to_string()
+        );
+
+        let settings: SchedulerSettings = toml::from_str("max_per_day = \"11:00\"\n").unwrap();
+        assert_eq!(
+            settings.to_options().unwrap_err().to_string(),
+            "the maximum work per day (11:00) exceeds the legal limit of 10:00".to_string()
+        );
+
+        let settings: SchedulerSettings =
+            toml::from_str("working_hours = \"05:00-12:00\"\n").unwrap();
+        assert_eq!(
+            settings.to_options().unwrap_err().to_string(),
+            "the working hours (05:00 - 12:00) must be between 06:00 and 22:00".to_string()
+        );
+
+        let settings: SchedulerSettings = toml::from_str("min_chunk = \"07:00\"\n").unwrap();
+        assert_eq!(
+            settings.to_options().unwrap_err().to_string(),
+            "the minimum chunk (07:00) exceeds the daily limit (06:00)".to_string()
+        );
+
+        let settings: SchedulerSettings = toml::from_str("rounding = \"00:00\"\n").unwrap();
+        assert_eq!(
+            settings.to_options().unwrap_err().to_string(),
+            "the rounding must be greater than 00:00".to_string()
+        );
+    }
+}
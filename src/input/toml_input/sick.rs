@@ -0,0 +1,113 @@
+use serde::Deserialize;
+
+use crate::input::json_input::Entry;
+use crate::input::toml_input::AbsenceKey;
+use crate::input::Task;
+use crate::time::{Date, Month, TimeSpan, TimeStamp, WorkingDuration, Year};
+use crate::utils::MapEntry;
+
+fn default_action() -> String {
+    "Krankheit".to_string()
+}
+
+/// Days on which one has been sick, like `[sick.24-28]`.
+///
+/// The hours that would have been worked on those days are credited
+/// towards the working time of the month, like it is done for vacation.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Sick {
+    #[serde(skip)]
+    key: AbsenceKey,
+    #[serde(default = "default_action")]
+    action: String,
+    #[serde(default)]
+    start: Option<TimeStamp>,
+}
+
+impl Sick {
+    /// The first day of the sick leave.
+    #[must_use]
+    pub const fn day(&self) -> usize {
+        match self.key {
+            AbsenceKey::Day(day) => day,
+            AbsenceKey::Range { start, .. } => start,
+        }
+    }
+
+    /// The last day of the sick leave.
+    #[must_use]
+    pub const fn last_day(&self) -> usize {
+        match self.key {
+            AbsenceKey::Day(day) => day,
+            AbsenceKey::Range { end, .. } => end,
+        }
+    }
+
+    #[must_use]
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// Makes the entries for the days of the sick leave.
+    ///
+    /// `hours_on` returns the time spans in which one would have worked on that
+    /// day. If a start has been specified, they are merged into one entry.
+    pub fn to_entries(
+        &self,
+        year: Year,
+        month: Month,
+        hours_on: impl Fn(Date) -> Vec<TimeSpan>,
+        mut schedule: impl FnMut(Task) -> Vec<(Date, TimeSpan)>,
+    ) -> anyhow::Result<Vec<Entry>> {
+        let mut result = Vec::new();
+
+        for day in self.day()..=self.last_day() {
+            let date = Date::new(year, month, day)?;
+            let mut spans = hours_on(date);
+
+            if let Some(start) = self.start {
+                let duration = spans
+                    .iter()
+                    .map(TimeSpan::duration)
+                    .sum::<WorkingDuration>();
+                spans = vec![TimeSpan::new(start, start + duration)];
+            }
+
+            for span in spans {
+                if span.duration() == WorkingDuration::default() {
+                    continue;
+                }
+
+                let scheduled = schedule(
+                    Task::new_duration(span.duration())
+                        .with_start(span.start())
+                        .with_suggested_date(date)
+                        .with_can_be_split(false),
+                );
+
+                if scheduled.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "Failed to schedule the sick leave on the day {}. Please make sure that there is enough free time on that day.",
+                        day
+                    ));
+                }
+
+                result.extend(scheduled.into_iter().map(|(date, span)| {
+                    Entry::new_sick(&self.action, date.day(), span.start(), span.end())
+                }));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<'de> MapEntry<'de> for Sick {
+    type Key = AbsenceKey;
+    type Value = Self;
+
+    fn new(key: Self::Key, mut value: Self::Value) -> Self {
+        value.key = key;
+        value
+    }
+}
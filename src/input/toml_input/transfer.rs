@@ -157,16 +157,21 @@ impl Add<Transfer> for WorkingDuration {
     }
 }
 
-impl fmt::Debug for Transfer {
+impl fmt::Display for Transfer {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let (sign, net_transfer) = self.net_transfer();
-        f.debug_tuple("Transfer")
-            .field(&format!(
-                "{}{}",
-                sign.symbol(),
-                WorkingDuration::from(net_transfer)
-            ))
-            .finish()
+        write!(
+            f,
+            "{}{}",
+            sign.symbol(),
+            WorkingDuration::from(net_transfer)
+        )
+    }
+}
+
+impl fmt::Debug for Transfer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Transfer").field(&self.to_string()).finish()
     }
 }
 
@@ -0,0 +1,283 @@
+use core::fmt;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::input::toml_input::{self, Contract, Holiday};
+use crate::input::Config;
+use crate::time::{Date, Month, WorkingDuration, Year};
+
+/// A problem with the vacation that has been taken in a year.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VacationProblem {
+    #[error("took {taken} of vacation, which exceeds the available {available}")]
+    OverTaken {
+        taken: WorkingDuration,
+        available: WorkingDuration,
+    },
+    #[error("holidays have been taken for {taken} months, but the contract has {expected} months in the year")]
+    HolidayMonths { taken: usize, expected: usize },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct VacationMonth {
+    taken: WorkingDuration,
+    holiday_months: usize,
+}
+
+/// Keeps track of the vacation that has been taken in a year of a contract.
+#[derive(Debug, Clone)]
+pub struct VacationLedger {
+    year: Year,
+    contract: Contract,
+    carry_over: WorkingDuration,
+    months: BTreeMap<Month, VacationMonth>,
+}
+
+impl VacationLedger {
+    #[must_use]
+    pub fn new(year: Year, contract: Contract) -> Self {
+        Self {
+            year,
+            contract,
+            carry_over: WorkingDuration::default(),
+            months: BTreeMap::new(),
+        }
+    }
+
+    /// The vacation that has not been taken in the previous year.
+    #[must_use]
+    pub fn with_carry_over(mut self, carry_over: WorkingDuration) -> Self {
+        self.carry_over = carry_over;
+        self
+    }
+
+    /// Makes a ledger from the month files of the contract in that year.
+    ///
+    /// Months of other years or departments are ignored.
+    pub fn from_months(
+        global: &toml_input::Global,
+        department: &str,
+        year: Year,
+        months: impl IntoIterator<Item = toml_input::Month>,
+    ) -> anyhow::Result<Self> {
+        let contract = global
+            .contract(department)
+            .ok_or_else(|| anyhow::anyhow!("no contract for department `{}`", department))?
+            .clone();
+
+        let mut ledger = Self::new(year, contract);
+
+        for month in months {
+            if month.general().year() != year || month.general().department() != department {
+                continue;
+            }
+
+            let holiday_months = month.holidays().map(Holiday::months).sum();
+            let month_number = month.general().month();
+            let config = Config::try_from_toml(month, global.clone())?
+                .build()
+                .map_err(|error| {
+                    error.context(format!(
+                        "failed to build the month {}-{:02}",
+                        year, month_number
+                    ))
+                })?;
+
+            ledger.add(&config, holiday_months);
+        }
+
+        Ok(ledger)
+    }
+
+    /// Reads all month files in the directory and makes a ledger for each contract
+    /// with months in that year.
+    ///
+    /// Files that are not month files (like the global file) are skipped.
+    pub fn from_directory(
+        global: impl AsRef<Path>,
+        directory: impl AsRef<Path>,
+        year: Year,
+    ) -> anyhow::Result<Vec<Self>> {
+        let global_path = global.as_ref();
+        let global = toml_input::Global::from_file(global_path)?;
+
+        let months = toml_input::Month::from_directory(directory, global_path)?
+            .into_iter()
+            .map(|(_, month)| month)
+            .collect::<Vec<_>>();
+
+        let departments = months
+            .iter()
+            .filter(|month| month.general().year() == year)
+            .map(|month| month.general().department().to_string())
+            .collect::<BTreeSet<_>>();
+
+        departments
+            .into_iter()
+            .map(|department| Self::from_months(&global, &department, year, months.clone()))
+            .collect()
+    }
+
+    /// Adds the vacation of the month, `holiday_months` is for how many months
+    /// the holiday in that month has been taken.
+    pub fn add(&mut self, config: &Config, holiday_months: usize) {
+        let taken = config
+            .month()
+            .to_month_file()
+            .entries()
+            .iter()
+            .filter(|entry| entry.is_vacation())
+            .map(|entry| entry.work_duration())
+            .sum();
+
+        self.months.insert(
+            config.month().month(),
+            VacationMonth {
+                taken,
+                holiday_months,
+            },
+        );
+    }
+
+    /// Returns the months of the year in which the contract is active.
+    #[must_use]
+    pub fn contract_months(&self) -> usize {
+        Month::months()
+            .into_iter()
+            .filter(|month| {
+                Date::first_day(self.year, *month) <= self.contract.end_date()
+                    && Date::last_day(self.year, *month) >= self.contract.start_date()
+            })
+            .count()
+    }
+
+    /// How much vacation one can take in the year (without the carry-over).
+    #[must_use]
+    pub fn entitlement(&self) -> WorkingDuration {
+        Holiday::duration(
+            self.contract.expected_working_duration(),
+            self.contract_months(),
+        )
+    }
+
+    #[must_use]
+    pub fn available(&self) -> WorkingDuration {
+        self.entitlement() + self.carry_over
+    }
+
+    #[must_use]
+    pub fn taken(&self) -> WorkingDuration {
+        self.months.values().map(|month| month.taken).sum()
+    }
+
+    #[must_use]
+    pub fn remaining(&self) -> WorkingDuration {
+        self.available().saturating_sub(self.taken())
+    }
+
+    /// Returns the remaining vacation in days.
+    #[must_use]
+    pub fn remaining_days(&self) -> f64 {
+        let day = Holiday::day_duration(self.contract.expected_working_duration());
+        if day == WorkingDuration::default() {
+            return 0.0;
+        }
+
+        f64::from(self.remaining().as_mins()) / f64::from(day.as_mins())
+    }
+
+    /// Returns `true` if more vacation has been taken than is available.
+    ///
+    /// The vacation of each month is rounded to full minutes, so up to a minute
+    /// per month is tolerated.
+    #[must_use]
+    pub fn is_over_taken(&self) -> bool {
+        let tolerance = WorkingDuration::from_mins(self.months.len() as u16);
+
+        self.taken() > self.available() + tolerance
+    }
+
+    #[must_use]
+    pub fn holiday_months(&self) -> usize {
+        self.months.values().map(|month| month.holiday_months).sum()
+    }
+
+    /// Checks that not too much vacation has been taken and that at the end of
+    /// the year, holidays have been taken for each month of the contract.
+    #[must_use]
+    pub fn problems(&self) -> Vec<VacationProblem> {
+        let mut problems = Vec::new();
+
+        if self.is_over_taken() {
+            problems.push(VacationProblem::OverTaken {
+                taken: self.taken(),
+                available: self.available(),
+            });
+        }
+
+        let expected = self.contract_months();
+        let is_complete = self.months.len() >= expected;
+        if self.holiday_months() > expected || (is_complete && self.holiday_months() < expected) {
+            problems.push(VacationProblem::HolidayMonths {
+                taken: self.holiday_months(),
+                expected,
+            });
+        }
+
+        problems
+    }
+}
+
+impl fmt::Display for VacationLedger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "vacation {} ({})", self.year, self.contract.department())?;
+        writeln!(f)?;
+        writeln!(f, "{:<7}  {:>8}  {:>6}", "Month", "Taken", "Months")?;
+
+        for (month, entry) in &self.months {
+            writeln!(
+                f,
+                "{:04}-{:02}  {:>8}  {:>6}",
+                self.year,
+                month.as_usize(),
+                entry.taken.to_string(),
+                entry.holiday_months
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>12}  {:>8}  ({} months)",
+            "entitlement:",
+            self.entitlement().to_string(),
+            self.contract_months()
+        )?;
+        writeln!(
+            f,
+            "{:>12}  {:>8}",
+            "carry-over:",
+            self.carry_over.to_string()
+        )?;
+        writeln!(f, "{:>12}  {:>8}", "taken:", self.taken().to_string())?;
+
+        if self.is_over_taken() {
+            writeln!(
+                f,
+                "{:>12}  {:>8}",
+                "over-taken:",
+                (self.taken() - self.available()).to_string()
+            )
+        } else {
+            writeln!(
+                f,
+                "{:>12}  {:>8}  ({:.1} days)",
+                "remaining:",
+                self.remaining().to_string(),
+                self.remaining_days()
+            )
+        }
+    }
+}
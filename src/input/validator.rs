@@ -0,0 +1,511 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use crate::input::json_input::{Entry, MonthFile};
+use crate::input::Config;
+use crate::latex_template::LatexTemplate;
+use crate::time::holiday::HolidayCalendar;
+use crate::time::{Date, TimeStamp, WeekDay, WorkingDuration};
+use crate::{time_stamp, working_duration};
+
+/// A rule of the time sheet that is broken by the month.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Violation {
+    #[error("day {day}: \"{action}\" is not a valid day of the month")]
+    InvalidDay { day: usize, action: String },
+    #[error("{date}: \"{action}\" starts at {start}, which is after its end at {end}")]
+    StartAfterEnd {
+        date: Date,
+        action: String,
+        start: TimeStamp,
+        end: TimeStamp,
+    },
+    #[error("{date}: \"{first}\" overlaps with \"{second}\"")]
+    Overlap {
+        date: Date,
+        first: String,
+        second: String,
+    },
+    #[error(
+        "{date}: working for {duration} requires a pause of at least {required}, but only {pause} have been taken ({})",
+        .actions.join(", ")
+    )]
+    MissingPause {
+        date: Date,
+        actions: Vec<String>,
+        duration: WorkingDuration,
+        pause: WorkingDuration,
+        required: WorkingDuration,
+    },
+    #[error(
+        "{date}: worked {duration}, which exceeds the daily limit of {limit} ({})",
+        .actions.join(", ")
+    )]
+    ExceedsDailyLimit {
+        date: Date,
+        actions: Vec<String>,
+        duration: WorkingDuration,
+        limit: WorkingDuration,
+    },
+    #[error(
+        "{date}: \"{action}\" is outside of the allowed working hours ({earliest} - {latest})"
+    )]
+    OutOfBounds {
+        date: Date,
+        action: String,
+        earliest: TimeStamp,
+        latest: TimeStamp,
+    },
+    #[error("{date}: \"{action}\" is on a sunday")]
+    Sunday { date: Date, action: String },
+    #[error("{date}: \"{action}\" is on a holiday")]
+    Holiday { date: Date, action: String },
+    #[error("{date}: \"{action}\" is outside of the contract ({start} - {end})")]
+    OutsideContract {
+        date: Date,
+        action: String,
+        start: Date,
+        end: Date,
+    },
+    #[error("worked {worked} in the month, which exceeds the target of {target}")]
+    ExceedsMonthlyTarget {
+        worked: WorkingDuration,
+        target: WorkingDuration,
+    },
+    #[error("the month has {count} entries, but at most {maximum} fit on the time sheet")]
+    TooManyEntries { count: usize, maximum: usize },
+}
+
+/// Checks that a month does not break any of the rules the TimeSheetGenerator enforces.
+#[derive(Debug, Clone)]
+pub struct Validator {
+    expected_working_duration: WorkingDuration,
+    contract_period: Option<(Date, Date)>,
+    holidays: HolidayCalendar,
+}
+
+impl Validator {
+    const EARLIEST_START: TimeStamp = time_stamp!(06:00);
+    const LATEST_END: TimeStamp = time_stamp!(22:00);
+    const DAILY_LIMIT: WorkingDuration = working_duration!(10:00);
+    /// How long one can work (including pauses) before a pause of the given
+    /// length is required.
+    const PAUSE_RULES: [(WorkingDuration, WorkingDuration); 2] = [
+        (working_duration!(06:00), working_duration!(00:30)),
+        (working_duration!(09:00), working_duration!(00:45)),
+    ];
+
+    /// Creates a validator for a contract, where one has to work
+    /// `expected_working_duration` each month (without transfer).
+    #[must_use]
+    pub fn new(expected_working_duration: WorkingDuration) -> Self {
+        Self {
+            expected_working_duration,
+            contract_period: None,
+            holidays: HolidayCalendar::default(),
+        }
+    }
+
+    /// Entries are only allowed between the start and end date (inclusive).
+    #[must_use]
+    pub fn with_contract_period(mut self, start: Date, end: Date) -> Self {
+        self.contract_period = Some((start, end));
+        self
+    }
+
+    /// Work on the holidays of the calendar is reported.
+    #[must_use]
+    pub fn with_holidays(mut self, holidays: HolidayCalendar) -> Self {
+        self.holidays = holidays;
+        self
+    }
+
+    #[must_use]
+    pub fn validate(&self, month: &MonthFile) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        let mut entries = month.entries().iter().collect::<Vec<_>>();
+        entries.sort();
+
+        // the rows of the template limit how many entries fit on the time sheet:
+        let maximum = LatexTemplate::rows();
+        if entries.len() > maximum {
+            violations.push(Violation::TooManyEntries {
+                count: entries.len(),
+                maximum,
+            });
+        }
+
+        let mut days: BTreeMap<Date, Vec<&Entry>> = BTreeMap::new();
+        for entry in entries {
+            let Ok(date) = Date::new(month.year(), month.month(), entry.day()) else {
+                violations.push(Violation::InvalidDay {
+                    day: entry.day(),
+                    action: entry.action().to_string(),
+                });
+                continue;
+            };
+
+            let span = entry.time_span();
+            if span.start() > span.end() {
+                violations.push(Violation::StartAfterEnd {
+                    date,
+                    action: entry.action().to_string(),
+                    start: span.start(),
+                    end: span.end(),
+                });
+                continue;
+            }
+
+            self.validate_entry(date, entry, &mut violations);
+            days.entry(date).or_default().push(entry);
+        }
+
+        for (date, entries) in days {
+            Self::validate_day(date, &entries, &mut violations);
+        }
+
+        // the time from the previous month counts as already worked and the time
+        // for the next month has to be worked in addition to the expected time.
+        let transfer = month.transfer();
+        let worked = month
+            .entries()
+            .iter()
+            .filter(|entry| entry.time_span().start() <= entry.time_span().end())
+            .map(Entry::work_duration)
+            .sum::<WorkingDuration>()
+            + transfer.previous();
+        let target = self.expected_working_duration + transfer.next();
+        if worked > target {
+            violations.push(Violation::ExceedsMonthlyTarget { worked, target });
+        }
+
+        violations
+    }
+
+    fn validate_entry(&self, date: Date, entry: &Entry, violations: &mut Vec<Violation>) {
+        let action = entry.action().to_string();
+        let span = entry.time_span();
+
+        if span.start() < Self::EARLIEST_START || span.end() > Self::LATEST_END {
+            violations.push(Violation::OutOfBounds {
+                date,
+                action: action.clone(),
+                earliest: Self::EARLIEST_START,
+                latest: Self::LATEST_END,
+            });
+        }
+
+        if date.week_day() == WeekDay::Sunday {
+            violations.push(Violation::Sunday {
+                date,
+                action: action.clone(),
+            });
+        } else if date.is_holiday(&self.holidays) {
+            violations.push(Violation::Holiday {
+                date,
+                action: action.clone(),
+            });
+        }
+
+        if let Some((start, end)) = self.contract_period {
+            if date < start || date > end {
+                violations.push(Violation::OutsideContract {
+                    date,
+                    action,
+                    start,
+                    end,
+                });
+            }
+        }
+    }
+
+    fn validate_day(date: Date, entries: &[&Entry], violations: &mut Vec<Violation>) {
+        // the entries are sorted by their start, so an entry overlaps with an
+        // earlier one, if it starts before the latest end so far:
+        let mut latest: Option<&Entry> = None;
+        for entry in entries {
+            if let Some(previous) = latest {
+                if previous.time_span().end() > entry.time_span().start() {
+                    violations.push(Violation::Overlap {
+                        date,
+                        first: previous.action().to_string(),
+                        second: entry.action().to_string(),
+                    });
+                }
+            }
+
+            if latest.is_none_or(|previous| entry.time_span().end() > previous.time_span().end()) {
+                latest = Some(entry);
+            }
+        }
+
+        let work = entries
+            .iter()
+            .filter(|entry| !entry.is_vacation() && !entry.is_sick())
+            .collect::<Vec<_>>();
+        let actions = || work.iter().map(|e| e.action().to_string()).collect();
+
+        let duration = work
+            .iter()
+            .map(|e| e.work_duration())
+            .sum::<WorkingDuration>();
+        if duration > Self::DAILY_LIMIT {
+            violations.push(Violation::ExceedsDailyLimit {
+                date,
+                actions: actions(),
+                duration,
+                limit: Self::DAILY_LIMIT,
+            });
+        }
+
+        // like the TimeSheetGenerator, the pauses are included in the duration:
+        let total = work
+            .iter()
+            .map(|e| e.time_span().duration())
+            .sum::<WorkingDuration>();
+        let pause = work
+            .iter()
+            .map(|e| e.break_duration())
+            .sum::<WorkingDuration>();
+
+        if let Some((_, required)) = Self::PAUSE_RULES
+            .iter()
+            .rev()
+            .find(|(threshold, required)| total >= *threshold && pause < *required)
+        {
+            violations.push(Violation::MissingPause {
+                date,
+                actions: actions(),
+                duration: total,
+                pause,
+                required: *required,
+            });
+        }
+    }
+}
+
+impl From<&Config> for Validator {
+    fn from(config: &Config) -> Self {
+        Self::new(config.month().real_expected_working_duration())
+            .with_contract_period(config.contract().start_date(), config.contract().end_date())
+            .with_holidays(config.month().holidays().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::input::Transfer;
+    use crate::time::{Month, Year};
+    use crate::{date, time_stamp, transfer};
+
+    #[must_use]
+    fn make_month(transfer: Transfer, entries: Vec<Entry>) -> MonthFile {
+        MonthFile::new(Year::new(2022), Month::November, transfer, entries)
+    }
+
+    #[test]
+    fn test_valid_month() {
+        let month = make_month(
+            transfer!(+00:00),
+            vec![
+                Entry::new("a", 2, time_stamp!(08:00), time_stamp!(12:00), None),
+                Entry::new("b", 2, time_stamp!(12:00), time_stamp!(13:00), None),
+                Entry::new("c", 3, time_stamp!(09:00), time_stamp!(16:00), None),
+            ],
+        );
+
+        assert_eq!(
+            Validator::new(working_duration!(40:00)).validate(&month),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_start_after_end() {
+        // such an entry can only be read from a month file:
+        let entry: Entry = serde_json::from_str(
+            r#"{ "action": "a", "day": 2, "start": "12:00", "end": "08:00" }"#,
+        )
+        .unwrap();
+        let month = make_month(transfer!(+00:00), vec![entry]);
+
+        assert_eq!(
+            Validator::new(working_duration!(40:00)).validate(&month),
+            vec![Violation::StartAfterEnd {
+                date: date!(2022:11:02),
+                action: "a".to_string(),
+                start: time_stamp!(12:00),
+                end: time_stamp!(08:00),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_overlap() {
+        let month = make_month(
+            transfer!(+00:00),
+            vec![
+                Entry::new("a", 2, time_stamp!(08:00), time_stamp!(10:00), None),
+                Entry::new("b", 2, time_stamp!(09:30), time_stamp!(11:00), None),
+            ],
+        );
+
+        assert_eq!(
+            Validator::new(working_duration!(40:00)).validate(&month),
+            vec![Violation::Overlap {
+                date: date!(2022:11:02),
+                first: "a".to_string(),
+                second: "b".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_overlap_with_earlier_entry() {
+        // "c" does not overlap with "b", but with "a", which ends after "b":
+        let month = make_month(
+            transfer!(+00:00),
+            vec![
+                Entry::new("a", 2, time_stamp!(08:00), time_stamp!(12:00), None),
+                Entry::new("b", 2, time_stamp!(09:00), time_stamp!(10:00), None),
+                Entry::new("c", 2, time_stamp!(11:00), time_stamp!(11:30), None),
+            ],
+        );
+
+        assert_eq!(
+            Validator::new(working_duration!(40:00)).validate(&month),
+            vec![
+                Violation::Overlap {
+                    date: date!(2022:11:02),
+                    first: "a".to_string(),
+                    second: "b".to_string(),
+                },
+                Violation::Overlap {
+                    date: date!(2022:11:02),
+                    first: "a".to_string(),
+                    second: "c".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_too_many_entries() {
+        let count = LatexTemplate::rows() + 1;
+        let month = make_month(
+            transfer!(+00:00),
+            (1..=count)
+                .map(|day| Entry::new("a", day, time_stamp!(08:00), time_stamp!(09:00), None))
+                .collect(),
+        );
+
+        assert!(Validator::new(working_duration!(40:00))
+            .validate(&month)
+            .contains(&Violation::TooManyEntries {
+                count,
+                maximum: count - 1,
+            }));
+    }
+
+    #[test]
+    fn test_missing_pause() {
+        let month = make_month(
+            transfer!(+00:00),
+            vec![
+                Entry::new("a", 2, time_stamp!(08:00), time_stamp!(12:00), None),
+                Entry::new("b", 2, time_stamp!(12:00), time_stamp!(15:00), None),
+            ],
+        );
+
+        assert_eq!(
+            Validator::new(working_duration!(40:00)).validate(&month),
+            vec![Violation::MissingPause {
+                date: date!(2022:11:02),
+                actions: vec!["a".to_string(), "b".to_string()],
+                duration: working_duration!(07:00),
+                pause: working_duration!(00:00),
+                required: working_duration!(00:30),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_sunday_and_holiday() {
+        let month = make_month(
+            transfer!(+00:00),
+            vec![
+                // 2022-11-01 is all saints day
+                Entry::new("a", 1, time_stamp!(08:00), time_stamp!(10:00), None),
+                Entry::new("b", 6, time_stamp!(08:00), time_stamp!(10:00), None),
+            ],
+        );
+
+        assert_eq!(
+            Validator::new(working_duration!(40:00)).validate(&month),
+            vec![
+                Violation::Holiday {
+                    date: date!(2022:11:01),
+                    action: "a".to_string(),
+                },
+                Violation::Sunday {
+                    date: date!(2022:11:06),
+                    action: "b".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exceeds_monthly_target() {
+        let month = make_month(
+            transfer!(-01:00),
+            vec![Entry::new(
+                "a",
+                2,
+                time_stamp!(08:00),
+                time_stamp!(12:00),
+                None,
+            )],
+        );
+
+        assert_eq!(
+            Validator::new(working_duration!(04:00)).validate(&month),
+            vec![Violation::ExceedsMonthlyTarget {
+                worked: working_duration!(05:00),
+                target: working_duration!(04:00),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_outside_contract() {
+        let month = make_month(
+            transfer!(+00:00),
+            vec![Entry::new(
+                "a",
+                30,
+                time_stamp!(08:00),
+                time_stamp!(10:00),
+                None,
+            )],
+        );
+
+        assert_eq!(
+            Validator::new(working_duration!(40:00))
+                .with_contract_period(date!(2022:01:01), date!(2022:11:29))
+                .validate(&month),
+            vec![Violation::OutsideContract {
+                date: date!(2022:11:30),
+                action: "a".to_string(),
+                start: date!(2022:01:01),
+                end: date!(2022:11:29),
+            }]
+        );
+    }
+}
@@ -5,7 +5,9 @@ use anyhow::Context;
 use log::{debug, info};
 use tempfile::TempDir;
 
+use crate::input::toml_input::Generator;
 use crate::input::Config;
+use crate::latex_template::LatexTemplate;
 use crate::tex_render::TexRender;
 use crate::utils::{self, Resources};
 
@@ -33,30 +35,18 @@ impl<'a> LatexGenerator<'a> {
         Self { config }
     }
 
-    pub fn generate(self, outpath: impl AsRef<Path>) -> anyhow::Result<()> {
-        let temp_dir = {
-            if let Some(dir) = self.config.preserve_dir() {
-                dir.to_path_buf()
-            } else {
-                TempDir::new()?.into_path()
-            }
-        };
-
-        // ensure the temp_dir exists:
-        utils::create_dir_all(&temp_dir)?;
-
+    /// Generates the latex file through the embedded TimeSheetGenerator.
+    fn generate_with_jar(&self, temp_dir: &Path) -> anyhow::Result<String> {
         let month_path = temp_dir.join("month.json");
         let global_path = temp_dir.join("global.json");
         let jar =
             Resources::get("TimeSheetGenerator.jar").expect("jar should be embedded in the binary");
         let jar_path = temp_dir.join("TimeSheetGenerator.jar");
 
-        debug!("temp_dir: {}", temp_dir.display());
         utils::write(&jar_path, jar.data)?;
         self.config.write_month_json(&month_path)?;
         self.config.write_global_json(&global_path)?;
 
-        info!("Generating latex file");
         let latex_file = dunce::canonicalize(temp_dir)?.join("output.tex");
         debug!("latex_file: {}", latex_file.display());
         let output = Command::new("java")
@@ -70,11 +60,33 @@ impl<'a> LatexGenerator<'a> {
             return Err(anyhow::anyhow!(String::from_utf8(output.stdout)?));
         }
 
+        Ok(utils::read_to_string(&latex_file)?)
+    }
+
+    /// Returns the latex file of the time sheet, that can be compiled to a pdf.
+    pub fn to_latex(&self) -> anyhow::Result<String> {
+        let temp_dir = {
+            if let Some(dir) = self.config.preserve_dir() {
+                dir.to_path_buf()
+            } else {
+                TempDir::new()?.into_path()
+            }
+        };
+
+        // ensure the temp_dir exists:
+        utils::create_dir_all(&temp_dir)?;
+        debug!("temp_dir: {}", temp_dir.display());
+
+        info!("Generating latex file");
+        let latex_source = match self.config.generator() {
+            Generator::Native => LatexTemplate::new(self.config).render()?,
+            Generator::Jar => self.generate_with_jar(&temp_dir)?,
+        };
+
         info!("Done");
-        info!("Compiling latex file to pdf");
 
         // fix the latex file, so it does compile:
-        let mut latex_file_content = inject_fix(utils::read_to_string(&latex_file)?.lines());
+        let mut latex_file_content = inject_fix(latex_source.lines());
 
         info!("Successfully read latex file");
 
@@ -108,6 +120,14 @@ impl<'a> LatexGenerator<'a> {
             )
             .replace("& 40:00", &format!("& {}", working_duration));
 
+        Ok(latex_file_content)
+    }
+
+    pub fn render(self) -> anyhow::Result<Vec<u8>> {
+        let latex_file_content = self.to_latex()?;
+
+        info!("Compiling latex file to pdf");
+
         let logo_file = "Latex_Logo.pdf";
         let mut renderer = TexRender::from_bytes(latex_file_content.into_bytes())?;
         renderer.add_asset_from_bytes(
@@ -135,10 +155,10 @@ impl<'a> LatexGenerator<'a> {
             renderer.preserve_dir(dir);
         }
 
-        utils::write(outpath, renderer.render()?)?;
+        let pdf = renderer.render()?;
 
         info!("Done");
 
-        Ok(())
+        Ok(pdf)
     }
 }
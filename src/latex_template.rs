@@ -0,0 +1,281 @@
+use std::borrow::Cow;
+
+use crate::input::json_input::{Entry, GlobalFile, MonthFile};
+use crate::input::{Config, WorkingArea};
+use crate::time::WorkingDuration;
+use crate::utils::Resources;
+
+const TEMPLATE_FILE: &str = "MiLoG_Template.tex";
+
+/// Placeholders in the template that are replaced with values of the whole sheet.
+///
+/// The order matters, they are replaced in the same order as the java tool does it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum SheetElement {
+    Year,
+    Month,
+    EmployeeName,
+    EmployeeId,
+    WorkingArea,
+    Department,
+    WorkingTime,
+    Wage,
+    Vacation,
+    Sum,
+    TransferPrevious,
+    TransferNext,
+}
+
+impl SheetElement {
+    const ALL: [Self; 12] = [
+        Self::Year,
+        Self::Month,
+        Self::EmployeeName,
+        Self::EmployeeId,
+        Self::WorkingArea,
+        Self::Department,
+        Self::WorkingTime,
+        Self::Wage,
+        Self::Vacation,
+        Self::Sum,
+        Self::TransferPrevious,
+        Self::TransferNext,
+    ];
+
+    #[must_use]
+    const fn placeholder(&self) -> &'static str {
+        match self {
+            Self::Year => "!year",
+            Self::Month => "!month",
+            Self::EmployeeName => "!employeeName",
+            Self::EmployeeId => "!employeeID",
+            Self::WorkingArea => "!workingArea",
+            Self::Department => "!department",
+            Self::WorkingTime => "!workingTime",
+            Self::Wage => "!wage",
+            Self::Vacation => "!vacation",
+            Self::Sum => "!sum",
+            Self::TransferPrevious => "!carryPred",
+            Self::TransferNext => "!carrySucc",
+        }
+    }
+}
+
+/// Placeholders in a single row of the table.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RowElement {
+    Action,
+    Date,
+    Start,
+    End,
+    Pause,
+    Duration,
+}
+
+impl RowElement {
+    const ALL: [Self; 6] = [
+        Self::Action,
+        Self::Date,
+        Self::Start,
+        Self::End,
+        Self::Pause,
+        Self::Duration,
+    ];
+
+    #[must_use]
+    const fn placeholder(&self) -> &'static str {
+        match self {
+            Self::Action => "!action",
+            Self::Date => "!date",
+            Self::Start => "!begin",
+            Self::End => "!end",
+            Self::Pause => "!break",
+            Self::Duration => "!dayTotal",
+        }
+    }
+}
+
+/// Escapes the text like the TimeSheetGenerator does it, so the output is identical.
+#[must_use]
+fn escape_text(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let replacement = match c {
+            '\\' => "\\textbackslash",
+            '~' => "\\textasciitilde",
+            '^' => "\\textasciicircum",
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(c);
+                continue;
+            }
+            _ => {
+                result.push(c);
+                continue;
+            }
+        };
+
+        result.push_str(replacement);
+        // the command would swallow the following space, so it has to be escaped:
+        if chars.peek() == Some(&' ') {
+            result.push('\\');
+        } else {
+            result.push(' ');
+        }
+    }
+
+    result
+}
+
+/// Generates the latex source of the time sheet, without relying on the TimeSheetGenerator.
+///
+/// The output is the same as the one from the java tool.
+pub struct LatexTemplate<'a> {
+    global: &'a GlobalFile,
+    month: MonthFile,
+}
+
+impl<'a> LatexTemplate<'a> {
+    #[must_use]
+    pub fn new(config: &'a Config) -> Self {
+        Self {
+            global: config.global_file(),
+            month: config.month().to_month_file(),
+        }
+    }
+
+    /// Returns how many entries fit on a single time sheet.
+    #[must_use]
+    pub(crate) fn rows() -> usize {
+        let template = Resources::get(TEMPLATE_FILE).expect("template should be embedded");
+
+        String::from_utf8_lossy(&template.data)
+            .matches(RowElement::Action.placeholder())
+            .count()
+    }
+
+    #[must_use]
+    fn entries(&self) -> Vec<&Entry> {
+        let mut entries = self.month.entries().iter().collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    #[must_use]
+    fn vacation_duration(&self) -> WorkingDuration {
+        self.month
+            .entries()
+            .iter()
+            .filter(|entry| entry.is_vacation())
+            .map(Entry::work_duration)
+            .sum()
+    }
+
+    #[must_use]
+    fn total_duration(&self) -> WorkingDuration {
+        self.month.entries().iter().map(Entry::work_duration).sum()
+    }
+
+    #[must_use]
+    fn sheet_value(&self, element: SheetElement) -> Cow<'_, str> {
+        match element {
+            SheetElement::Year => self.month.year().to_string().into(),
+            SheetElement::Month => self.month.month().to_string().into(),
+            SheetElement::EmployeeName => escape_text(self.global.name()).into(),
+            SheetElement::EmployeeId => self.global.staff_id().to_string().into(),
+            SheetElement::WorkingArea => match self.global.working_area() {
+                WorkingArea::LargeScaleResearchSector => {
+                    "\\textbf{GF:} $\\boxtimes$ \\textbf{UB:} $\\Box$".into()
+                }
+                WorkingArea::Universitary => {
+                    "\\textbf{GF:} $\\Box$ \\textbf{UB:} $\\boxtimes$".into()
+                }
+            },
+            SheetElement::Department => escape_text(self.global.department()).into(),
+            SheetElement::WorkingTime => self.global.expected_working_duration().to_string().into(),
+            SheetElement::Wage => format!("{:?}", self.global.wage()).into(),
+            SheetElement::Vacation => self.vacation_duration().to_string().into(),
+            SheetElement::Sum => self.total_duration().to_string().into(),
+            SheetElement::TransferPrevious => self.month.transfer().previous().to_string().into(),
+            SheetElement::TransferNext => self.month.transfer().next().to_string().into(),
+        }
+    }
+
+    #[must_use]
+    fn row_value(&self, entry: &Entry, element: RowElement) -> String {
+        match element {
+            RowElement::Action => escape_text(entry.action()),
+            RowElement::Date => format!(
+                "{:02}.{:02}.{:02}",
+                entry.day(),
+                self.month.month().as_usize(),
+                self.month.year().as_usize() % 100
+            ),
+            RowElement::Start => entry.time_span().start().to_string(),
+            RowElement::End => entry.time_span().end().to_string(),
+            RowElement::Pause => entry.break_duration().to_string(),
+            RowElement::Duration => {
+                if entry.is_vacation() {
+                    format!("{} U", entry.work_duration())
+                } else if entry.is_sick() {
+                    format!("{} K", entry.work_duration())
+                } else {
+                    entry.work_duration().to_string()
+                }
+            }
+        }
+    }
+
+    pub fn render(&self) -> anyhow::Result<String> {
+        let template = Resources::get(TEMPLATE_FILE).expect("template should be embedded");
+        let mut result = String::from_utf8(template.data.into_owned())?;
+
+        let rows = Self::rows();
+        let entries = self.entries();
+        if entries.len() > rows {
+            return Err(anyhow::anyhow!(
+                "the time sheet has {} entries, but only {} fit on a single sheet",
+                entries.len(),
+                rows
+            ));
+        }
+
+        for element in SheetElement::ALL {
+            result = result.replace(element.placeholder(), &self.sheet_value(element));
+        }
+
+        for entry in entries {
+            for element in RowElement::ALL {
+                result = result.replacen(element.placeholder(), &self.row_value(entry, element), 1);
+            }
+        }
+
+        // clear the remaining rows:
+        for element in RowElement::ALL {
+            result = result.replace(element.placeholder(), "");
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(
+            escape_text("filler & co_x"),
+            "filler \\& co\\_x".to_string()
+        );
+        assert_eq!(escape_text("100% {done}"), "100\\% \\{done\\}".to_string());
+        assert_eq!(escape_text("a\\b"), "a\\textbackslash b".to_string());
+        assert_eq!(escape_text("a~ b"), "a\\textasciitilde\\ b".to_string());
+        assert_eq!(escape_text("Übung"), "Übung".to_string());
+    }
+}
@@ -8,18 +8,37 @@
 
 mod latex_generator;
 mod latex_string;
+mod latex_template;
+mod render_cache;
 mod tex_render;
 mod utils;
 
 pub mod input;
+pub mod renderer;
 pub mod time;
 
 use log::{info, warn};
 
 use crate::input::Config;
-use crate::latex_generator::LatexGenerator;
+use crate::latex_template::LatexTemplate;
+use crate::render_cache::RenderCache;
+use crate::renderer::SheetRenderer;
 
-pub fn generate_time_sheet(config: &Config) -> anyhow::Result<()> {
+/// Generates the time sheet and writes it to the output of the config.
+///
+/// If the renderer is cacheable and the output has already been rendered from
+/// the same month, global file and signature, it is not rendered again.
+pub fn generate_time_sheet(config: &Config, renderer: &dyn SheetRenderer) -> anyhow::Result<()> {
+    generate(config, renderer, false)
+}
+
+/// Like [`generate_time_sheet`], but renders the time sheet even if the output
+/// is up to date.
+pub fn regenerate_time_sheet(config: &Config, renderer: &dyn SheetRenderer) -> anyhow::Result<()> {
+    generate(config, renderer, true)
+}
+
+fn generate(config: &Config, renderer: &dyn SheetRenderer, force: bool) -> anyhow::Result<()> {
     let total_time = config.month().total_working_time();
     info!("worked: {}", total_time);
 
@@ -30,16 +49,42 @@ pub fn generate_time_sheet(config: &Config) -> anyhow::Result<()> {
         );
     }
 
-    info!("generating time sheet from month and global files");
+    let output = config.output();
+    let cache = renderer
+        .is_cacheable()
+        .then(|| RenderCache::new(config, renderer))
+        .transpose()?;
 
-    let generator = LatexGenerator::new(config);
+    if !force && cache.as_ref().is_some_and(RenderCache::is_up_to_date) {
+        info!(
+            "`{}` is up to date, use `--force` to make it again",
+            output.display()
+        );
+        return Ok(());
+    }
+
+    info!(
+        "generating time sheet from month and global files with `{}`",
+        renderer.name()
+    );
 
-    let output = config.output();
     if let Some(parent) = output.parent() {
         utils::create_dir_all(parent)?;
     }
 
-    generator.generate(output)?;
+    utils::write(output, renderer.render(config)?)?;
+
+    if let Some(cache) = cache {
+        cache.store()?;
+    }
 
     Ok(())
 }
+
+/// Generates the latex source of the time sheet.
+///
+/// This is the same file the TimeSheetGenerator would output, before it is
+/// adjusted (signature, background, ...) and compiled to a pdf.
+pub fn generate_latex(config: &Config) -> anyhow::Result<String> {
+    LatexTemplate::new(config).render()
+}
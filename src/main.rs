@@ -3,17 +3,29 @@
 use std::env;
 use std::ffi::OsStr;
 use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::Context as _;
 use lettre::message::header::ContentType;
 use lettre::message::{Attachment, SinglePart};
 use lettre::Transport;
-use log::{error, info};
+use log::{error, info, warn};
 use seahorse::{App, Command, Context, Flag};
 
-use time_sheet::generate_time_sheet;
-use time_sheet::input::Config;
+use time_sheet::input::toml_input;
+use time_sheet::input::{
+    Config, GlobalScaffold, MonthScaffold, OvertimeLedger, VacationLedger, Validator,
+};
+use time_sheet::renderer::{LatexPdfRenderer, Renderers, SheetRenderer, TableRenderer};
+use time_sheet::time::{WorkingDuration, Year};
+use time_sheet::{generate_time_sheet, regenerate_time_sheet};
 
 fn set_env_if_absent<K: AsRef<OsStr>, V: AsRef<OsStr>>(var: K, default: impl FnOnce() -> V) {
     if env::var(var.as_ref()).is_err() {
@@ -106,13 +118,34 @@ mod seahorse_exts {
 
 use seahorse_exts::{ContextExt, TryActionExt};
 
-fn build_config(global: &Path, month: &Path, output: &Path) -> anyhow::Result<Config> {
+fn build_config(
+    global: &Path,
+    month: &Path,
+    output: &Path,
+    renderer: &dyn SheetRenderer,
+) -> anyhow::Result<Config> {
     let mut config = Config::try_from_toml_files(month, global)?;
 
-    config.output(output);
+    config.output(output).extension(renderer.extension());
 
     let config = config.build()?;
 
+    for dropped in config.dropped_entries() {
+        warn!(
+            "`{}` on {} has not been added, because {}",
+            dropped.entry().action(),
+            dropped.date(),
+            dropped.reason()
+        );
+    }
+
+    for (action, date) in config.unmatched_overrides() {
+        warn!(
+            "the override of `{}` on {} does not match an occurrence of the event",
+            action, date
+        );
+    }
+
     info!("finished building config");
 
     Ok(config)
@@ -136,6 +169,23 @@ fn make_extract_context_flags(context: &Context) -> anyhow::Result<(PathBuf, Pat
     Ok((global, month, output))
 }
 
+fn renderer_from_context<'a>(
+    context: &Context,
+    renderers: &'a Renderers,
+) -> anyhow::Result<&'a dyn SheetRenderer> {
+    let name = context
+        .string_flag("format")
+        .unwrap_or_else(|_| LatexPdfRenderer.name().to_string());
+
+    renderers.get(&name).ok_or_else(|| {
+        anyhow::anyhow!(
+            "unknown format \"{}\", available formats: {}",
+            name,
+            renderers.names().collect::<Vec<_>>().join(", ")
+        )
+    })
+}
+
 fn attachment_from_file(path: impl AsRef<Path>) -> anyhow::Result<SinglePart> {
     let path = path.as_ref();
 
@@ -171,7 +221,7 @@ fn send(config: &Config, recipient: &str, subject: &str, keep_pdf: bool) -> anyh
             &format!("{:02}", config.month().month().as_usize()),
         );
 
-    make(&config)?;
+    make(config, &LatexPdfRenderer, false)?;
 
     let email = mail
         .builder()
@@ -185,7 +235,7 @@ fn send(config: &Config, recipient: &str, subject: &str, keep_pdf: bool) -> anyh
         recipient, &subject
     );
 
-    mail.to_transport().send(&email).with_context(|| {
+    mail.to_transport()?.send(&email).with_context(|| {
         format!(
             "failed to send email to \"{}\" with subject \"{}\"",
             recipient, subject
@@ -202,8 +252,334 @@ fn send(config: &Config, recipient: &str, subject: &str, keep_pdf: bool) -> anyh
     Ok(())
 }
 
-fn make(config: &Config) -> anyhow::Result<()> {
-    generate_time_sheet(config)?;
+fn make(config: &Config, renderer: &dyn SheetRenderer, force: bool) -> anyhow::Result<()> {
+    if force {
+        regenerate_time_sheet(config, renderer)?;
+    } else {
+        generate_time_sheet(config, renderer)?;
+    }
+
+    Ok(())
+}
+
+/// Makes the time sheet of a single month of `make --all`, returning the path of
+/// the output file.
+fn make_month(
+    global: &Path,
+    month: &Path,
+    output: Option<&Path>,
+    renderer: &dyn SheetRenderer,
+    force: bool,
+) -> anyhow::Result<PathBuf> {
+    let output = output.map_or_else(|| month.with_file_name("pdfs"), Path::to_path_buf);
+    // the output must be a directory, so each month gets its own file
+    fs::create_dir_all(&output)
+        .with_context(|| format!("failed to create `{}`", output.display()))?;
+
+    let config = build_config(global, month, &output, renderer)?;
+    make(&config, renderer, force)?;
+
+    Ok(config.output().to_path_buf())
+}
+
+fn make_all(
+    global: &Path,
+    workspace: &Path,
+    output: Option<&Path>,
+    renderer: &dyn SheetRenderer,
+    force: bool,
+) -> anyhow::Result<()> {
+    let months = toml_input::Month::find_files(workspace, global)?;
+
+    if months.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no month files in `{}`",
+            workspace.display()
+        ));
+    }
+
+    let global_file = toml_input::Global::from_file(global)?;
+
+    // the temporary files of the months would be mixed up in the preserve_dir
+    let workers = if global_file.preserve_dir().is_some() {
+        1
+    } else {
+        thread::available_parallelism()
+            .map_or(1, NonZeroUsize::get)
+            .min(months.len())
+    };
+
+    info!(
+        "making {} month(s) with {} thread(s)",
+        months.len(),
+        workers
+    );
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(month) = months.get(index) else {
+                    break;
+                };
+
+                // a panic in one month should not stop the others
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    make_month(global, month, output, renderer, force)
+                }))
+                .unwrap_or_else(|_| Err(anyhow::anyhow!("panicked while making the month")));
+
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut failed = 0;
+    for (index, result) in results {
+        match result {
+            Ok(path) => println!(
+                "{:<6}  {} -> {}",
+                "ok",
+                months[index].display(),
+                path.display()
+            ),
+            Err(error) => {
+                failed += 1;
+                println!("{:<6}  {}: {:#}", "failed", months[index].display(), error);
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow::anyhow!(
+            "{} of {} month(s) failed",
+            failed,
+            months.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns when the last time one of the files has been modified, a missing
+/// file is `None`.
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// Blocks until one of the files has been modified.
+///
+/// Editors often save a file in multiple steps, so this waits until the files
+/// did not change for a short time.
+fn wait_for_changes(paths: &[PathBuf], times: &mut Vec<Option<SystemTime>>) {
+    const POLL_INTERVAL: Duration = Duration::from_millis(200);
+    const DEBOUNCE: Duration = Duration::from_millis(500);
+
+    let mut last_change = None;
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = modified_times(paths);
+        if current != *times {
+            *times = current;
+            last_change = Some(Instant::now());
+        } else if last_change.is_some_and(|instant| instant.elapsed() >= DEBOUNCE) {
+            return;
+        }
+    }
+}
+
+/// Makes the time sheet every time the global, month or signature file is saved.
+///
+/// Errors are printed and the files are watched further. Through the render
+/// cache, the pdf is only compiled again if the scheduled month, the global
+/// file or the signature changed.
+fn watch(
+    global: &Path,
+    month: &Path,
+    output: &Path,
+    renderer: &dyn SheetRenderer,
+    force: bool,
+) -> anyhow::Result<()> {
+    let mut paths = vec![global.to_path_buf(), month.to_path_buf()];
+    let mut times = modified_times(&paths);
+
+    loop {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| -> anyhow::Result<_> {
+            let config = build_config(global, month, output, renderer)?;
+            make(&config, renderer, force)?;
+
+            Ok(config.signature().map(|s| s.path().to_path_buf()))
+        }))
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("panicked while making the month")));
+
+        match result {
+            Ok(signature) => {
+                // changes during the build are noticed, because only the time
+                // of a new signature is read
+                if paths.get(2) != signature.as_ref() {
+                    paths.truncate(2);
+                    times.truncate(2);
+                    paths.extend(signature);
+                    times.extend(modified_times(&paths[2..]));
+                }
+            }
+            // without the backtrace, which would hide the error in the output
+            Err(error) => error!("{:#}", error),
+        }
+
+        info!(
+            "watching {} for changes",
+            paths
+                .iter()
+                .map(|path| format!("`{}`", path.display()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        wait_for_changes(&paths, &mut times);
+    }
+}
+
+fn validate(config: &Config) -> anyhow::Result<()> {
+    let violations = Validator::from(config).validate(&config.month().to_month_file());
+
+    if violations.is_empty() {
+        info!("the month is valid");
+        return Ok(());
+    }
+
+    for violation in &violations {
+        error!("{}", violation);
+    }
+
+    Err(anyhow::anyhow!(
+        "the month has {} rule violation(s)",
+        violations.len()
+    ))
+}
+
+fn show(config: &Config) -> anyhow::Result<()> {
+    print!("{}", String::from_utf8(TableRenderer.render(config)?)?);
+
+    Ok(())
+}
+
+fn vacation(
+    global: &Path,
+    directory: &Path,
+    year: Year,
+    carry_over: WorkingDuration,
+) -> anyhow::Result<()> {
+    let ledgers = VacationLedger::from_directory(global, directory, year)?;
+
+    if ledgers.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no month files of {} in `{}`",
+            year,
+            directory.display()
+        ));
+    }
+
+    let mut problems = 0;
+    for ledger in ledgers {
+        let ledger = ledger.with_carry_over(carry_over);
+        println!("{}", ledger);
+
+        for problem in ledger.problems() {
+            error!("{}", problem);
+            problems += 1;
+        }
+    }
+
+    if problems > 0 {
+        return Err(anyhow::anyhow!("the vacation has {} problem(s)", problems));
+    }
+
+    Ok(())
+}
+
+fn overtime(global: &Path, directory: &Path, fill: bool) -> anyhow::Result<()> {
+    let ledgers = OvertimeLedger::from_directory(global, directory)?;
+
+    if ledgers.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no month files in `{}`",
+            directory.display()
+        ));
+    }
+
+    let mut problems = 0;
+    for ledger in ledgers {
+        println!("{}", ledger);
+
+        for problem in ledger.problems() {
+            error!("{}", problem);
+            problems += 1;
+        }
+
+        if fill {
+            for path in ledger.fill_in()? {
+                info!("filled in the transfer of `{}`", path.display());
+            }
+        }
+    }
+
+    if problems > 0 {
+        return Err(anyhow::anyhow!("the overtime has {} problem(s)", problems));
+    }
+
+    Ok(())
+}
+
+fn new_month(global: &Path, previous: &Path, output: Option<PathBuf>) -> anyhow::Result<()> {
+    let scaffold = MonthScaffold::from_files(global, previous)?;
+
+    let output = output.unwrap_or_else(|| {
+        previous.with_file_name(format!(
+            "{:04}-{:02}.toml",
+            scaffold.year(),
+            scaffold.month().as_usize()
+        ))
+    });
+
+    if output.exists() {
+        return Err(anyhow::anyhow!("`{}` already exists", output.display()));
+    }
+
+    fs::write(&output, scaffold.to_string())
+        .with_context(|| format!("failed to write `{}`", output.display()))?;
+
+    info!("created `{}`", output.display());
+
+    Ok(())
+}
+
+fn init(output: &Path) -> anyhow::Result<()> {
+    if output.exists() {
+        return Err(anyhow::anyhow!("`{}` already exists", output.display()));
+    }
+
+    let scaffold = GlobalScaffold::ask(&mut io::stdin().lock(), &mut io::stdout())?;
+    // make sure that the file can be loaded
+    scaffold.to_global()?;
+
+    fs::write(output, scaffold.to_string())
+        .with_context(|| format!("failed to write `{}`", output.display()))?;
+
+    info!("created `{}`", output.display());
 
     Ok(())
 }
@@ -218,15 +594,53 @@ fn run() -> anyhow::Result<()> {
             Flag::new("global", seahorse::FlagType::String).description("Path to the global file."),
         )
         .flag(Flag::new("month", seahorse::FlagType::String).description("Path to the month file."))
+        .flag(Flag::new("all", seahorse::FlagType::String).description(
+            "[optional] Makes all month files in the folder (or matching the glob, like `2022/*.toml`) instead of a single month.",
+        ))
         .flag(
             Flag::new("output", seahorse::FlagType::String).description(
                 "[optional] Path to the output folder. Default: `<path to month>/pdfs/`",
             ),
         )
+        .flag(Flag::new("format", seahorse::FlagType::String).description(
+            "[optional] The format of the output file (latex-pdf, latex, json, csv, table). Default: latex-pdf",
+        ))
+        .flag(Flag::new("watch", seahorse::FlagType::Bool).description(
+            "[optional] Makes the time sheet again, every time the global, month or signature file is saved.",
+        ))
+        .flag(Flag::new("force", seahorse::FlagType::Bool).description(
+            "[optional] Makes the pdf, even if it is up to date. Default: false",
+        ))
         .try_action(|context: &Context| {
+            let renderers = Renderers::default();
+            let renderer = renderer_from_context(context, &renderers)?;
+
+            if context.bool_flag("watch") && context.string_flag("all").is_ok() {
+                return Err(anyhow::anyhow!("`--watch` can not be used together with `--all`"));
+            }
+
+            let force = context.bool_flag("force");
+
+            if context.bool_flag("watch") {
+                let (global, month, output) = make_extract_context_flags(context)?;
+                return watch(&global, &month, &output, renderer, force);
+            }
+
+            if let Ok(workspace) = context.string_flag("all") {
+                let global = context.required_path_flag("global")?;
+                let output = context.string_flag("output").ok().map(PathBuf::from);
+                return make_all(
+                    &global,
+                    Path::new(&workspace),
+                    output.as_deref(),
+                    renderer,
+                    force,
+                );
+            }
+
             let (global, month, output) = make_extract_context_flags(context)?;
-            let config = build_config(&global, &month, &output)?;
-            make(&config)
+            let config = build_config(&global, &month, &output, renderer)?;
+            make(&config, renderer, force)
         });
 
     let send_command = Command::new("send")
@@ -247,7 +661,7 @@ fn run() -> anyhow::Result<()> {
         .flag(Flag::new("keep-pdf", seahorse::FlagType::Bool).description("[optional] Keeps the pdf file after sending the email. Default: false"))
         .try_action(|context: &Context| {
             let (global, month, output) = make_extract_context_flags(context)?;
-            let config = build_config(&global, &month, &output)?;
+            let config = build_config(&global, &month, &output, &LatexPdfRenderer)?;
 
             let subject = context.required_string_flag("subject")?;
 
@@ -263,13 +677,137 @@ fn run() -> anyhow::Result<()> {
             send(&config, recipient, &subject, keep_pdf)
         });
 
+    let validate_command = Command::new("validate")
+        .usage(format!("{} validate [args]", args[0]))
+        .description("Checks that the time sheet from the given files does not break any rules.")
+        .flag(
+            Flag::new("global", seahorse::FlagType::String).description("Path to the global file."),
+        )
+        .flag(Flag::new("month", seahorse::FlagType::String).description("Path to the month file."))
+        .try_action(|context: &Context| {
+            let (global, month, output) = make_extract_context_flags(context)?;
+            let config = build_config(&global, &month, &output, &LatexPdfRenderer)?;
+            validate(&config)
+        });
+
+    let show_command = Command::new("show")
+        .usage(format!("{} show [args]", args[0]))
+        .description("Prints the scheduled entries of the month as a table.")
+        .flag(
+            Flag::new("global", seahorse::FlagType::String).description("Path to the global file."),
+        )
+        .flag(Flag::new("month", seahorse::FlagType::String).description("Path to the month file."))
+        .try_action(|context: &Context| {
+            let (global, month, output) = make_extract_context_flags(context)?;
+            let config = build_config(&global, &month, &output, &TableRenderer)?;
+            show(&config)
+        });
+
+    let vacation_command = Command::new("vacation")
+        .usage(format!("{} vacation [args]", args[0]))
+        .description("Sums up the vacation of a year and checks it against the entitlement.")
+        .flag(
+            Flag::new("global", seahorse::FlagType::String).description("Path to the global file."),
+        )
+        .flag(
+            Flag::new("months", seahorse::FlagType::String)
+                .description("Path to the folder with the month files of the year."),
+        )
+        .flag(Flag::new("year", seahorse::FlagType::Int).description("The year to check."))
+        .flag(
+            Flag::new("carry-over", seahorse::FlagType::String).description(
+                "[optional] The vacation that is left from the previous year. Default: 00:00",
+            ),
+        )
+        .try_action(|context: &Context| {
+            let global = context.required_path_flag("global")?;
+            let months = context.required_path_flag("months")?;
+            let year = context
+                .int_flag("year")
+                .map_err(|_| anyhow::anyhow!("missing required flag \"year\""))?;
+            let year = usize::try_from(year)
+                .map_err(|_| anyhow::anyhow!("the year must not be negative, but was {}", year))?;
+            let carry_over = context
+                .string_flag("carry-over")
+                .ok()
+                .map(|value| value.parse::<WorkingDuration>())
+                .transpose()?
+                .unwrap_or_default();
+
+            vacation(&global, &months, Year::new(year), carry_over)
+        });
+
+    let overtime_command = Command::new("overtime")
+        .usage(format!("{} overtime [args]", args[0]))
+        .description("Sums up the overtime of consecutive months and checks their transfers.")
+        .flag(
+            Flag::new("global", seahorse::FlagType::String).description("Path to the global file."),
+        )
+        .flag(
+            Flag::new("months", seahorse::FlagType::String)
+                .description("Path to the folder with the month files."),
+        )
+        .flag(Flag::new("fill", seahorse::FlagType::Bool).description(
+            "[optional] Writes the transfer into the month files that do not have one. Default: false",
+        ))
+        .try_action(|context: &Context| {
+            let global = context.required_path_flag("global")?;
+            let months = context.required_path_flag("months")?;
+
+            overtime(&global, &months, context.bool_flag("fill"))
+        });
+
+    let new_month_command = Command::new("new-month")
+        .usage(format!("{} new-month [args]", args[0]))
+        .description("Creates the month file for the month after the given one.")
+        .flag(
+            Flag::new("global", seahorse::FlagType::String).description("Path to the global file."),
+        )
+        .flag(
+            Flag::new("month", seahorse::FlagType::String)
+                .description("Path to the month file of the previous month."),
+        )
+        .flag(
+            Flag::new("output", seahorse::FlagType::String).description(
+                "[optional] Path to the new month file. Default: `<folder of month>/<year>-<month>.toml`",
+            ),
+        )
+        .try_action(|context: &Context| {
+            let global = context.required_path_flag("global")?;
+            let month = context.required_path_flag("month")?;
+            let output = context.string_flag("output").ok().map(PathBuf::from);
+
+            new_month(&global, &month, output)
+        });
+
+    let init_command = Command::new("init")
+        .usage(format!("{} init [args]", args[0]))
+        .description("Asks for the data of the contract and creates the global file.")
+        .flag(
+            Flag::new("output", seahorse::FlagType::String)
+                .description("[optional] Path to the global file. Default: `global.toml`"),
+        )
+        .try_action(|context: &Context| {
+            let output = context
+                .string_flag("output")
+                .map_or_else(|_| PathBuf::from("global.toml"), PathBuf::from);
+
+            init(&output)
+        });
+
     let app = App::new(env!("CARGO_PKG_NAME"))
         .description(env!("CARGO_PKG_DESCRIPTION"))
         .author(env!("CARGO_PKG_AUTHORS"))
         .version(env!("CARGO_PKG_VERSION"))
         .usage(format!("{} [args]", args[0]))
         .command(make_command)
-        .command(send_command);
+        .command(send_command)
+        .command(validate_command)
+        .command(show_command)
+        .command(vacation_command)
+        .command(overtime_command)
+        .command(new_month_command)
+        .command(init_command);
 
     app.run(args);
 
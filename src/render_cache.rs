@@ -0,0 +1,143 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::PathBuf;
+
+use crate::input::Config;
+use crate::renderer::SheetRenderer;
+use crate::utils::{self, Resources};
+
+/// The 64-bit FNV-1a hash, which (unlike the hasher of the standard library)
+/// is the same for every build, so it can be stored in a file.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    /// The name of the algorithm, which is stored together with the digest.
+    const NAME: &'static str = "fnv1a-64";
+
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Adds a field, which is prefixed with its length, so the boundaries
+    /// between the fields are part of the hash.
+    fn field(&mut self, bytes: impl AsRef<[u8]>) {
+        let bytes = bytes.as_ref();
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn digest(&self) -> String {
+        format!("{}:{:016x}", Self::NAME, self.0)
+    }
+}
+
+/// Remembers from which input the output file has been rendered, so it does not
+/// have to be rendered again if nothing changed.
+///
+/// The digest is stored in a hidden file next to the output, like
+/// `.2022-11.pdf.hash` for `2022-11.pdf`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderCache {
+    digest: String,
+    output: PathBuf,
+    path: PathBuf,
+}
+
+impl RenderCache {
+    /// Hashes everything that ends up in the output of the renderer.
+    pub fn new(config: &Config, renderer: &dyn SheetRenderer) -> anyhow::Result<Self> {
+        let mut hasher = Fnv1a::new();
+
+        // a different version might render the sheet differently
+        hasher.field(env!("CARGO_PKG_VERSION"));
+        // as well as a different template, jar or logo
+        let mut resources = Resources::iter().collect::<Vec<_>>();
+        resources.sort();
+        for name in resources {
+            let resource = Resources::get(&name).expect("resource should be embedded");
+            hasher.field(name.as_bytes());
+            hasher.field(resource.metadata.sha256_hash());
+        }
+        hasher.field(renderer.name());
+        hasher.field(format!("{:?}", config.generator()));
+
+        hasher.field(serde_json::to_string(&config.month().to_month_file())?);
+        hasher.field(serde_json::to_string(config.global_file())?);
+        hasher.field(
+            config
+                .bg_content()
+                .map(|content| content.to_string())
+                .unwrap_or_default(),
+        );
+        hasher.field(config.month().real_expected_working_duration().to_string());
+
+        if let Some(signature) = config.signature() {
+            hasher.field(signature.date().to_string());
+            hasher.field(signature.width().to_le_bytes());
+            hasher.field(
+                signature
+                    .path()
+                    .file_name()
+                    .unwrap_or_default()
+                    .as_encoded_bytes(),
+            );
+            hasher.field(utils::read(signature.path())?);
+        }
+
+        let output = config.output().to_path_buf();
+        let mut file_name = OsString::from(".");
+        file_name.push(output.file_name().unwrap_or_default());
+        file_name.push(".hash");
+
+        Ok(Self {
+            digest: hasher.digest(),
+            path: output.with_file_name(file_name),
+            output,
+        })
+    }
+
+    /// Returns `true` if the output exists and has been rendered from the same
+    /// input.
+    ///
+    /// A digest of a different algorithm never matches, so the output is
+    /// rendered again.
+    #[must_use]
+    pub fn is_up_to_date(&self) -> bool {
+        self.output.is_file()
+            && utils::read_to_string(&self.path).is_ok_and(|digest| digest.trim() == self.digest)
+    }
+
+    /// Remembers that the output has been rendered from the current input.
+    pub fn store(&self) -> io::Result<()> {
+        utils::write(&self.path, &self.digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_fnv1a() {
+        let mut hasher = Fnv1a::new();
+        assert_eq!(hasher.digest(), "fnv1a-64:cbf29ce484222325");
+
+        hasher.write(b"a");
+        assert_eq!(hasher.digest(), "fnv1a-64:af63dc4c8601ec8c");
+
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"foobar");
+        assert_eq!(hasher.digest(), "fnv1a-64:85944171f73967e8");
+    }
+}
@@ -0,0 +1,72 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::input::Config;
+use crate::renderer::SheetRenderer;
+use crate::time::Date;
+
+const HEADER: [&str; 8] = [
+    "date", "action", "start", "end", "pause", "duration", "vacation", "sick",
+];
+
+/// Quotes the field if it contains characters with a special meaning in csv.
+#[must_use]
+fn escape_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Outputs the entries of the month as comma separated values.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CsvRenderer;
+
+impl SheetRenderer for CsvRenderer {
+    fn name(&self) -> &str {
+        "csv"
+    }
+
+    fn extension(&self) -> &str {
+        "csv"
+    }
+
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>> {
+        let month = config.month().to_month_file();
+        let mut result = HEADER.join(",");
+        result.push('\n');
+
+        for entry in month.entries() {
+            let date = Date::new(month.year(), month.month(), entry.day())?;
+            writeln!(
+                result,
+                "{},{},{},{},{},{},{},{}",
+                date,
+                escape_field(entry.action()),
+                entry.time_span().start(),
+                entry.time_span().end(),
+                entry.break_duration(),
+                entry.work_duration(),
+                entry.is_vacation(),
+                entry.is_sick(),
+            )?;
+        }
+
+        Ok(result.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("task a"), "task a");
+        assert_eq!(escape_field("a, b"), "\"a, b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
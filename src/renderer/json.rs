@@ -0,0 +1,20 @@
+use crate::input::Config;
+use crate::renderer::SheetRenderer;
+
+/// Outputs the month in the json format of the TimeSheetGenerator.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct JsonRenderer;
+
+impl SheetRenderer for JsonRenderer {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn extension(&self) -> &str {
+        "json"
+    }
+
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>> {
+        Ok(config.to_month_json()?.into_bytes())
+    }
+}
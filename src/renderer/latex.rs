@@ -0,0 +1,47 @@
+use crate::input::Config;
+use crate::latex_generator::LatexGenerator;
+use crate::renderer::SheetRenderer;
+
+/// Renders the time sheet as a pdf (requires `latexmk`).
+#[derive(Debug, Copy, Clone, Default)]
+pub struct LatexPdfRenderer;
+
+impl SheetRenderer for LatexPdfRenderer {
+    fn name(&self) -> &str {
+        "latex-pdf"
+    }
+
+    fn extension(&self) -> &str {
+        "pdf"
+    }
+
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>> {
+        LatexGenerator::new(config).render()
+    }
+
+    fn is_cacheable(&self) -> bool {
+        true
+    }
+}
+
+/// Outputs the latex file of the time sheet, without compiling it.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct LatexRenderer;
+
+impl SheetRenderer for LatexRenderer {
+    fn name(&self) -> &str {
+        "latex"
+    }
+
+    fn extension(&self) -> &str {
+        "tex"
+    }
+
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>> {
+        Ok(LatexGenerator::new(config).to_latex()?.into_bytes())
+    }
+
+    fn is_cacheable(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,94 @@
+//! Backends that turn a fully scheduled [`Config`] into an output file.
+//!
+//! New formats can be added by implementing [`SheetRenderer`] and
+//! registering them in a [`Renderers`] registry.
+
+mod csv;
+mod json;
+mod latex;
+mod table;
+
+pub use self::csv::*;
+pub use json::*;
+pub use latex::*;
+pub use table::*;
+
+use crate::input::Config;
+
+/// Turns a config into a time sheet.
+///
+/// Renderers are shared between threads, when multiple months are made at once.
+pub trait SheetRenderer: Send + Sync {
+    /// The name with which the renderer can be selected, like `latex-pdf`.
+    #[must_use]
+    fn name(&self) -> &str;
+
+    /// The extension of the files created by this renderer (without the dot).
+    #[must_use]
+    fn extension(&self) -> &str;
+
+    /// Renders the time sheet described by the config.
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>>;
+
+    /// Whether the output is only rendered again, if the scheduled month, the
+    /// global file or the signature changed.
+    ///
+    /// This should be enabled for slow renderers, whose output only depends on
+    /// those.
+    #[must_use]
+    fn is_cacheable(&self) -> bool {
+        false
+    }
+}
+
+/// A collection of renderers, that can be looked up by their name.
+pub struct Renderers {
+    renderers: Vec<Box<dyn SheetRenderer>>,
+}
+
+impl Renderers {
+    /// Creates a registry without any renderers.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            renderers: Vec::new(),
+        }
+    }
+
+    /// Adds the renderer to the registry.
+    ///
+    /// If a renderer with the same name exists, it will be replaced.
+    pub fn register(&mut self, renderer: impl SheetRenderer + 'static) -> &mut Self {
+        self.renderers.retain(|r| r.name() != renderer.name());
+        self.renderers.push(Box::new(renderer));
+        self
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&dyn SheetRenderer> {
+        self.renderers
+            .iter()
+            .find(|renderer| renderer.name() == name)
+            .map(|renderer| renderer.as_ref())
+    }
+
+    /// Returns the names of all registered renderers.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.renderers.iter().map(|renderer| renderer.name())
+    }
+}
+
+impl Default for Renderers {
+    fn default() -> Self {
+        let mut result = Self::empty();
+
+        result
+            .register(LatexPdfRenderer)
+            .register(LatexRenderer)
+            .register(JsonRenderer)
+            .register(CsvRenderer)
+            .register(TableRenderer);
+
+        result
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Write;
+
+use crate::input::json_input::Entry;
+use crate::input::{Config, EntrySource};
+use crate::renderer::SheetRenderer;
+use crate::time::{Date, WorkingDuration};
+
+/// Renders the scheduled month as a plain text table, that can be printed in the terminal.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TableRenderer;
+
+impl TableRenderer {
+    fn write_row(
+        output: &mut String,
+        date: Date,
+        entry: &Entry,
+        source: EntrySource,
+    ) -> std::fmt::Result {
+        writeln!(
+            output,
+            "{:>3}  {:<9}  {}  {}  {}  {:>8}  {:<9}  {}",
+            date.day(),
+            // padding is only applied to strings
+            date.week_day().to_string(),
+            entry.time_span().start(),
+            entry.time_span().end(),
+            entry.break_duration(),
+            entry.work_duration().to_string(),
+            source.to_string(),
+            entry.action(),
+        )
+    }
+
+    fn write_total(output: &mut String, label: &str, total: WorkingDuration) -> std::fmt::Result {
+        writeln!(output, "{:>35}  {:>8}", label, total.to_string())
+    }
+}
+
+impl SheetRenderer for TableRenderer {
+    fn name(&self) -> &str {
+        "table"
+    }
+
+    fn extension(&self) -> &str {
+        "txt"
+    }
+
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>> {
+        let month = config.month();
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "{:>3}  {:<9}  {:<5}  {:<5}  {:<5}  {:>8}  {:<9}  Action",
+            "Day", "Weekday", "Start", "End", "Pause", "Duration", "Source"
+        )?;
+
+        let mut week = None;
+        let mut week_total = WorkingDuration::default();
+        let mut total = WorkingDuration::default();
+        let mut vacation = WorkingDuration::default();
+        let mut sick = WorkingDuration::default();
+        for (source, entry) in month.scheduled_entries() {
+            let date = Date::new(month.year(), month.month(), entry.day())?;
+
+            if let Some(number) = week.filter(|number| *number != date.week_number()) {
+                Self::write_total(&mut output, &format!("week {}:", number), week_total)?;
+                week_total = WorkingDuration::default();
+            }
+
+            week = Some(date.week_number());
+            week_total += entry.work_duration();
+            total += entry.work_duration();
+
+            if entry.is_vacation() {
+                vacation += entry.work_duration();
+            } else if entry.is_sick() {
+                sick += entry.work_duration();
+            }
+
+            Self::write_row(&mut output, date, &entry, source)?;
+        }
+
+        if let Some(number) = week {
+            Self::write_total(&mut output, &format!("week {}:", number), week_total)?;
+        }
+
+        let expected = month.expected_working_duration();
+        writeln!(output)?;
+        Self::write_total(&mut output, "total:", total)?;
+
+        // vacation and sick leave are part of the total, but listed separately
+        if vacation > WorkingDuration::default() {
+            Self::write_total(&mut output, "vacation:", vacation)?;
+        }
+
+        if sick > WorkingDuration::default() {
+            Self::write_total(&mut output, "sick:", sick)?;
+        }
+
+        Self::write_total(&mut output, "expected:", expected)?;
+
+        if total < expected {
+            Self::write_total(&mut output, "missing:", expected - total)?;
+        } else if total > expected {
+            Self::write_total(&mut output, "exceeding:", total - expected)?;
+        }
+
+        Ok(output.into_bytes())
+    }
+}
@@ -6,7 +6,8 @@ use core::str::FromStr;
 use serde::Deserialize;
 use thiserror::Error;
 
-use crate::time::{holiday, Month, WeekDay, Year};
+use crate::time::holiday::HolidayCalendar;
+use crate::time::{Month, WeekDay, Year};
 use crate::utils::StrExt;
 
 #[macro_export]
@@ -140,8 +141,8 @@ impl Date {
     }
 
     // TODO: write some good tests for this, also take care of https://github.com/kit-sdq/TimeSheetGenerator/pull/121
-    pub const fn is_holiday(&self) -> bool {
-        holiday::is_holiday(*self)
+    pub fn is_holiday(&self, calendar: &HolidayCalendar) -> bool {
+        calendar.is_holiday(*self)
     }
 
     #[must_use]
@@ -211,8 +212,8 @@ impl Date {
     }
 
     #[must_use]
-    pub const fn is_workday(&self) -> bool {
-        !self.is_holiday() && !self.week_day().is_eq(&WeekDay::Sunday)
+    pub fn is_workday(&self, calendar: &HolidayCalendar) -> bool {
+        !self.is_holiday(calendar) && !self.week_day().is_eq(&WeekDay::Sunday)
     }
 
     #[must_use]
@@ -1,9 +1,15 @@
-use crate::time::{Date, Month, WeekDay};
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::time::{Date, Month, WeekDay, WorkingDuration};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct HolidayEntry {
     name: &'static str,
     is_mandatory: bool,
+    /// How much of a normal workday can be worked on that day (in percent).
+    available_percent: u8,
 }
 
 impl HolidayEntry {
@@ -12,8 +18,35 @@ impl HolidayEntry {
         Self {
             name,
             is_mandatory: true,
+            available_percent: 0,
         }
     }
+
+    /// A day on which one only works half of the day, like christmas eve.
+    #[must_use]
+    pub const fn new_half_day(name: &'static str) -> Self {
+        Self {
+            name,
+            is_mandatory: false,
+            available_percent: 50,
+        }
+    }
+
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns `true` if one can not work at all on that day.
+    #[must_use]
+    pub const fn is_full_day(&self) -> bool {
+        self.available_percent == 0
+    }
+
+    #[must_use]
+    pub const fn available_percent(&self) -> u8 {
+        self.available_percent
+    }
 }
 
 /// Returns `true` when the given date is on easter sunday.
@@ -40,73 +73,383 @@ const fn is_easter_sunday(date: Date) -> bool {
     Month::new(n).is_eq(&date.month()) && o + 1 == date.day()
 }
 
-pub const fn get_holiday_entry(date: Date) -> Option<HolidayEntry> {
-    let fixed_holidays = [
-        (1, Month::January, HolidayEntry::new_mandatory("Neujahr")),
-        (
-            6,
-            Month::January,
-            HolidayEntry::new_mandatory("Heilige Drei Könige"),
-        ),
-        (1, Month::May, HolidayEntry::new_mandatory("Tag der Arbeit")),
-        (
-            3,
-            Month::October,
-            HolidayEntry::new_mandatory("Tag der deutschen Einheit"),
-        ),
-        (
-            1,
-            Month::November,
-            HolidayEntry::new_mandatory("Allerheiligen"),
-        ),
-        (
-            25,
-            Month::December,
-            HolidayEntry::new_mandatory("1. Weihnachtsfeiertag"),
-        ),
-        (
-            26,
-            Month::December,
-            HolidayEntry::new_mandatory("2. Weihnachtsfeiertag"),
-        ),
+/// The public holidays of a German state (Bundesland).
+///
+/// Holidays that only apply in some municipalities of a state are not included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum State {
+    #[default]
+    BadenWuerttemberg,
+    Bayern,
+    Berlin,
+    Brandenburg,
+    Bremen,
+    Hamburg,
+    Hessen,
+    MecklenburgVorpommern,
+    Niedersachsen,
+    NordrheinWestfalen,
+    RheinlandPfalz,
+    Saarland,
+    Sachsen,
+    SachsenAnhalt,
+    SchleswigHolstein,
+    Thueringen,
+}
+
+impl State {
+    /// All states, in the order of their codes.
+    pub const ALL: [Self; 16] = [
+        Self::BadenWuerttemberg,
+        Self::Bayern,
+        Self::Berlin,
+        Self::Brandenburg,
+        Self::Bremen,
+        Self::Hamburg,
+        Self::Hessen,
+        Self::MecklenburgVorpommern,
+        Self::Niedersachsen,
+        Self::NordrheinWestfalen,
+        Self::RheinlandPfalz,
+        Self::Saarland,
+        Self::Sachsen,
+        Self::SachsenAnhalt,
+        Self::SchleswigHolstein,
+        Self::Thueringen,
     ];
 
-    let mut i = 0;
-    while i < fixed_holidays.len() {
-        let (day, month, entry) = fixed_holidays[i];
+    /// Returns the abbreviation of the state, like `BW` for Baden-Württemberg.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::BadenWuerttemberg => "BW",
+            Self::Bayern => "BY",
+            Self::Berlin => "BE",
+            Self::Brandenburg => "BB",
+            Self::Bremen => "HB",
+            Self::Hamburg => "HH",
+            Self::Hessen => "HE",
+            Self::MecklenburgVorpommern => "MV",
+            Self::Niedersachsen => "NI",
+            Self::NordrheinWestfalen => "NW",
+            Self::RheinlandPfalz => "RP",
+            Self::Saarland => "SL",
+            Self::Sachsen => "SN",
+            Self::SachsenAnhalt => "ST",
+            Self::SchleswigHolstein => "SH",
+            Self::Thueringen => "TH",
+        }
+    }
+
+    /// Returns the holidays with a fixed date and whether they apply in this state.
+    const fn fixed_holidays(&self, year: usize) -> [(usize, Month, HolidayEntry, bool); 13] {
+        use State::*;
 
-        if date.day() == day && date.month().is_eq(&month) {
-            return Some(entry);
+        let state = *self;
+
+        [
+            (
+                1,
+                Month::January,
+                HolidayEntry::new_mandatory("Neujahr"),
+                true,
+            ),
+            (
+                6,
+                Month::January,
+                HolidayEntry::new_mandatory("Heilige Drei Könige"),
+                matches!(state, BadenWuerttemberg | Bayern | SachsenAnhalt),
+            ),
+            (
+                8,
+                Month::March,
+                HolidayEntry::new_mandatory("Internationaler Frauentag"),
+                (matches!(state, Berlin) && year >= 2019)
+                    || (matches!(state, MecklenburgVorpommern) && year >= 2023),
+            ),
+            (
+                1,
+                Month::May,
+                HolidayEntry::new_mandatory("Tag der Arbeit"),
+                true,
+            ),
+            (
+                15,
+                Month::August,
+                HolidayEntry::new_mandatory("Mariä Himmelfahrt"),
+                matches!(state, Saarland),
+            ),
+            (
+                20,
+                Month::September,
+                HolidayEntry::new_mandatory("Weltkindertag"),
+                matches!(state, Thueringen) && year >= 2019,
+            ),
+            (
+                3,
+                Month::October,
+                HolidayEntry::new_mandatory("Tag der deutschen Einheit"),
+                true,
+            ),
+            (
+                31,
+                Month::October,
+                HolidayEntry::new_mandatory("Reformationstag"),
+                matches!(
+                    state,
+                    Brandenburg | MecklenburgVorpommern | Sachsen | SachsenAnhalt | Thueringen
+                ) || (matches!(state, Bremen | Hamburg | Niedersachsen | SchleswigHolstein)
+                    && year >= 2018)
+                    // the 500th anniversary of the reformation was a holiday in all states
+                    || year == 2017,
+            ),
+            (
+                1,
+                Month::November,
+                HolidayEntry::new_mandatory("Allerheiligen"),
+                matches!(
+                    state,
+                    BadenWuerttemberg | Bayern | NordrheinWestfalen | RheinlandPfalz | Saarland
+                ),
+            ),
+            (
+                24,
+                Month::December,
+                HolidayEntry::new_half_day("Heiligabend"),
+                true,
+            ),
+            (
+                25,
+                Month::December,
+                HolidayEntry::new_mandatory("1. Weihnachtsfeiertag"),
+                true,
+            ),
+            (
+                26,
+                Month::December,
+                HolidayEntry::new_mandatory("2. Weihnachtsfeiertag"),
+                true,
+            ),
+            (
+                31,
+                Month::December,
+                HolidayEntry::new_half_day("Silvester"),
+                true,
+            ),
+        ]
+    }
+
+    /// Returns the holiday on the given date, if there is one in this state.
+    pub const fn get_holiday_entry(&self, date: Date) -> Option<HolidayEntry> {
+        use State::*;
+
+        let state = *self;
+        let fixed_holidays = self.fixed_holidays(date.year().as_usize());
+
+        let mut i = 0;
+        while i < fixed_holidays.len() {
+            let (day, month, entry, applies) = fixed_holidays[i];
+
+            if applies && date.day() == day && date.month().is_eq(&month) {
+                return Some(entry);
+            }
+
+            i += 1;
         }
 
-        i += 1;
+        if matches!(state, Brandenburg) && is_easter_sunday(date) {
+            return Some(HolidayEntry::new_mandatory("Ostersonntag"));
+        }
+
+        if is_easter_sunday(date.sub_days(1)) {
+            return Some(HolidayEntry::new_mandatory("Ostermontag"));
+        }
+
+        if date.week_day().is_eq(&WeekDay::Thursday) && is_easter_sunday(date.sub_days(39)) {
+            return Some(HolidayEntry::new_mandatory("Christi Himmelfahrt"));
+        }
+
+        if date.week_day().is_eq(&WeekDay::Friday) && is_easter_sunday(date.add_days(2)) {
+            return Some(HolidayEntry::new_mandatory("Karfreitag"));
+        }
+
+        if matches!(state, Brandenburg)
+            && date.week_day().is_eq(&WeekDay::Sunday)
+            && is_easter_sunday(date.sub_days(49))
+        {
+            return Some(HolidayEntry::new_mandatory("Pfingstsonntag"));
+        }
+
+        if date.week_day().is_eq(&WeekDay::Monday) && is_easter_sunday(date.sub_days(50)) {
+            return Some(HolidayEntry::new_mandatory("Pfingstmontag"));
+        }
+
+        if matches!(
+            state,
+            BadenWuerttemberg | Bayern | Hessen | NordrheinWestfalen | RheinlandPfalz | Saarland
+        ) && date.week_day().is_eq(&WeekDay::Thursday)
+            && is_easter_sunday(date.sub_days(60))
+        {
+            return Some(HolidayEntry::new_mandatory("Fronleichnam"));
+        }
+
+        // the wednesday before the 23rd of november
+        if matches!(state, Sachsen)
+            && date.month().is_eq(&Month::November)
+            && date.week_day().is_eq(&WeekDay::Wednesday)
+            && date.day() >= 16
+            && date.day() <= 22
+        {
+            return Some(HolidayEntry::new_mandatory("Buß- und Bettag"));
+        }
+
+        None
+    }
+
+    /// Returns `true` if the date is a holiday on which one can not work at all.
+    pub const fn is_holiday(&self, date: Date) -> bool {
+        match self.get_holiday_entry(date) {
+            Some(entry) => entry.is_full_day(),
+            None => false,
+        }
+    }
+}
+
+/// Days on which the institution is closed, like the days between christmas and new year.
+///
+/// They are treated like public holidays.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Closure {
+    #[serde(default)]
+    name: Option<String>,
+    start: Date,
+    /// The last day of the closure (inclusive), if it is longer than a single day.
+    #[serde(default)]
+    end: Option<Date>,
+    /// Whether the closure repeats every year on the same days.
+    #[serde(default)]
+    yearly: bool,
+}
+
+impl Closure {
+    #[must_use]
+    pub fn new(start: Date, end: Date) -> Self {
+        Self {
+            name: None,
+            start,
+            end: Some(end),
+            yearly: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_yearly(mut self, yearly: bool) -> Self {
+        self.yearly = yearly;
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns `true` if the institution is closed on that date.
+    ///
+    /// A yearly closure can span the new year, like from the 24th of december
+    /// to the 6th of january.
+    #[must_use]
+    pub fn contains(&self, date: Date) -> bool {
+        let end = self.end.unwrap_or(self.start);
+
+        if !self.yearly {
+            return self.start <= date && date <= end;
+        }
+
+        let day_of_year = |date: Date| (date.month().as_usize(), date.day());
+        let (start, end, date) = (day_of_year(self.start), day_of_year(end), day_of_year(date));
+
+        if start <= end {
+            start <= date && date <= end
+        } else {
+            date >= start || date <= end
+        }
+    }
+}
+
+/// The days on which one does not work, because of public holidays or closures.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    state: State,
+    closures: Vec<Closure>,
+}
+
+impl HolidayCalendar {
+    #[must_use]
+    pub fn new(state: State) -> Self {
+        Self {
+            state,
+            closures: Vec::new(),
+        }
     }
 
-    if is_easter_sunday(date.sub_days(1)) {
-        return Some(HolidayEntry::new_mandatory("Ostermontag"));
+    #[must_use]
+    pub fn with_closures(mut self, closures: Vec<Closure>) -> Self {
+        self.closures = closures;
+        self
     }
 
-    if date.week_day().is_eq(&WeekDay::Thursday) && is_easter_sunday(date.sub_days(39)) {
-        return Some(HolidayEntry::new_mandatory("Christi Himmelfahrt"));
+    #[must_use]
+    pub fn state(&self) -> State {
+        self.state
     }
 
-    if date.week_day().is_eq(&WeekDay::Friday) && is_easter_sunday(date.add_days(2)) {
-        return Some(HolidayEntry::new_mandatory("Karfreitag"));
+    /// Returns the closure on that date, if there is one.
+    #[must_use]
+    pub fn closure_on(&self, date: Date) -> Option<&Closure> {
+        self.closures.iter().find(|closure| closure.contains(date))
     }
 
-    if date.week_day().is_eq(&WeekDay::Monday) && is_easter_sunday(date.sub_days(50)) {
-        return Some(HolidayEntry::new_mandatory("Pfingstmontag"));
+    #[must_use]
+    pub fn is_holiday(&self, date: Date) -> bool {
+        self.state.is_holiday(date) || self.closure_on(date).is_some()
     }
 
-    if date.week_day().is_eq(&WeekDay::Thursday) && is_easter_sunday(date.sub_days(60)) {
-        return Some(HolidayEntry::new_mandatory("Fronleichnam"));
+    /// Returns how much of `duration` can be worked on that date.
+    ///
+    /// Nothing can be worked on holidays and only a part on days like christmas eve.
+    #[must_use]
+    pub fn available_time_on(&self, date: Date, duration: WorkingDuration) -> WorkingDuration {
+        if self.is_holiday(date) {
+            return WorkingDuration::default();
+        }
+
+        match self.state.get_holiday_entry(date) {
+            Some(entry) => WorkingDuration::from_mins(
+                (u32::from(duration.as_mins()) * u32::from(entry.available_percent()) / 100) as u16,
+            ),
+            None => duration,
+        }
     }
+}
+
+impl FromStr for State {
+    type Err = anyhow::Error;
 
-    None
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|state| state.code().eq_ignore_ascii_case(string))
+            .ok_or_else(|| anyhow::anyhow!("Unknown state: {}", string))
+    }
 }
 
-pub const fn is_holiday(date: Date) -> bool {
-    get_holiday_entry(date).is_some()
+impl TryFrom<String> for State {
+    type Error = <Self as FromStr>::Err;
+
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        Self::from_str(&string)
+    }
 }
 
 #[cfg(test)]
@@ -115,7 +458,8 @@ mod tests {
 
     use pretty_assertions::assert_eq;
 
-    use crate::date;
+    use crate::time::Year;
+    use crate::{date, working_duration};
 
     #[test]
     fn test_is_holiday() {
@@ -136,10 +480,15 @@ mod tests {
 
         for date in holidays[0]..=holidays[holidays.len() - 1] {
             if holidays.contains(&date) {
-                assert_eq!(is_holiday(date), true, "date {} should be a holiday", date);
+                assert_eq!(
+                    State::BadenWuerttemberg.is_holiday(date),
+                    true,
+                    "date {} should be a holiday",
+                    date
+                );
             } else {
                 assert_eq!(
-                    is_holiday(date),
+                    State::BadenWuerttemberg.is_holiday(date),
                     false,
                     "date {} should not be a holiday",
                     date
@@ -148,6 +497,288 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_state_holidays() {
+        // holidays in 2024 that apply in all states
+        let nationwide = [
+            date!(2024:01:01),
+            date!(2024:03:29),
+            date!(2024:04:01),
+            date!(2024:05:01),
+            date!(2024:05:09),
+            date!(2024:05:20),
+            date!(2024:10:03),
+            date!(2024:12:25),
+            date!(2024:12:26),
+        ];
+
+        let regional = [
+            (
+                State::BadenWuerttemberg,
+                vec![date!(2024:01:06), date!(2024:05:30), date!(2024:11:01)],
+            ),
+            (
+                State::Bayern,
+                vec![date!(2024:01:06), date!(2024:05:30), date!(2024:11:01)],
+            ),
+            (State::Berlin, vec![date!(2024:03:08)]),
+            (
+                State::Brandenburg,
+                vec![date!(2024:03:31), date!(2024:05:19), date!(2024:10:31)],
+            ),
+            (State::Bremen, vec![date!(2024:10:31)]),
+            (State::Hamburg, vec![date!(2024:10:31)]),
+            (State::Hessen, vec![date!(2024:05:30)]),
+            (
+                State::MecklenburgVorpommern,
+                vec![date!(2024:03:08), date!(2024:10:31)],
+            ),
+            (State::Niedersachsen, vec![date!(2024:10:31)]),
+            (
+                State::NordrheinWestfalen,
+                vec![date!(2024:05:30), date!(2024:11:01)],
+            ),
+            (
+                State::RheinlandPfalz,
+                vec![date!(2024:05:30), date!(2024:11:01)],
+            ),
+            (
+                State::Saarland,
+                vec![date!(2024:05:30), date!(2024:08:15), date!(2024:11:01)],
+            ),
+            (State::Sachsen, vec![date!(2024:10:31), date!(2024:11:20)]),
+            (
+                State::SachsenAnhalt,
+                vec![date!(2024:01:06), date!(2024:10:31)],
+            ),
+            (State::SchleswigHolstein, vec![date!(2024:10:31)]),
+            (
+                State::Thueringen,
+                vec![date!(2024:09:20), date!(2024:10:31)],
+            ),
+        ];
+
+        assert_eq!(regional.len(), State::ALL.len());
+
+        for (state, holidays) in regional {
+            for date in date!(2024:01:01)..=date!(2024:12:31) {
+                assert_eq!(
+                    state.is_holiday(date),
+                    nationwide.contains(&date) || holidays.contains(&date),
+                    "date {} in {}",
+                    date,
+                    state.code()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_holidays_of_year() {
+        for (state, year, holidays) in [
+            (
+                State::BadenWuerttemberg,
+                2017,
+                vec![
+                    date!(2017:01:01),
+                    date!(2017:01:06),
+                    date!(2017:04:14),
+                    date!(2017:04:17),
+                    date!(2017:05:01),
+                    date!(2017:05:25),
+                    date!(2017:06:05),
+                    date!(2017:06:15),
+                    date!(2017:10:03),
+                    date!(2017:10:31),
+                    date!(2017:11:01),
+                    date!(2017:12:25),
+                    date!(2017:12:26),
+                ],
+            ),
+            (
+                State::Berlin,
+                2017,
+                vec![
+                    date!(2017:01:01),
+                    date!(2017:04:14),
+                    date!(2017:04:17),
+                    date!(2017:05:01),
+                    date!(2017:05:25),
+                    date!(2017:06:05),
+                    date!(2017:10:03),
+                    date!(2017:10:31),
+                    date!(2017:12:25),
+                    date!(2017:12:26),
+                ],
+            ),
+            (
+                State::Niedersachsen,
+                2016,
+                vec![
+                    date!(2016:01:01),
+                    date!(2016:03:25),
+                    date!(2016:03:28),
+                    date!(2016:05:01),
+                    date!(2016:05:05),
+                    date!(2016:05:16),
+                    date!(2016:10:03),
+                    date!(2016:12:25),
+                    date!(2016:12:26),
+                ],
+            ),
+            (
+                State::BadenWuerttemberg,
+                2023,
+                vec![
+                    date!(2023:01:01),
+                    date!(2023:01:06),
+                    date!(2023:04:07),
+                    date!(2023:04:10),
+                    date!(2023:05:01),
+                    date!(2023:05:18),
+                    date!(2023:05:29),
+                    date!(2023:06:08),
+                    date!(2023:10:03),
+                    date!(2023:11:01),
+                    date!(2023:12:25),
+                    date!(2023:12:26),
+                ],
+            ),
+            (
+                State::Sachsen,
+                2025,
+                vec![
+                    date!(2025:01:01),
+                    date!(2025:04:18),
+                    date!(2025:04:21),
+                    date!(2025:05:01),
+                    date!(2025:05:29),
+                    date!(2025:06:09),
+                    date!(2025:10:03),
+                    date!(2025:10:31),
+                    date!(2025:11:19),
+                    date!(2025:12:25),
+                    date!(2025:12:26),
+                ],
+            ),
+        ] {
+            let year = Year::new(year);
+            let start = Date::new(year, Month::January, 1).unwrap();
+            let end = Date::new(year, Month::December, 31).unwrap();
+
+            assert_eq!(
+                (start..=end)
+                    .filter(|date| state.is_holiday(*date))
+                    .collect::<Vec<_>>(),
+                holidays,
+                "{} in {}",
+                year,
+                state.code()
+            );
+        }
+    }
+
+    #[test]
+    fn test_introduced_holidays() {
+        for (state, date) in [
+            (State::Berlin, date!(2019:03:08)),
+            (State::MecklenburgVorpommern, date!(2023:03:08)),
+            (State::Thueringen, date!(2019:09:20)),
+        ] {
+            assert_eq!(state.is_holiday(date), true, "{} in {}", date, state.code());
+
+            let year_before = Date::new(
+                Year::new(date.year().as_usize() - 1),
+                date.month(),
+                date.day(),
+            )
+            .unwrap();
+            assert_eq!(
+                state.is_holiday(year_before),
+                false,
+                "{} in {}",
+                year_before,
+                state.code()
+            );
+        }
+
+        // the reformationstag has been introduced in 2018, but was already
+        // a holiday in all states in 2017
+        assert_eq!(State::Niedersachsen.is_holiday(date!(2018:10:31)), true);
+        assert_eq!(State::Niedersachsen.is_holiday(date!(2017:10:31)), true);
+        assert_eq!(State::Niedersachsen.is_holiday(date!(2016:10:31)), false);
+
+        // buß- und bettag is always on the wednesday before the 23rd of november
+        for date in [
+            date!(2022:11:16),
+            date!(2023:11:22),
+            date!(2025:11:19),
+            date!(2026:11:18),
+        ] {
+            assert_eq!(State::Sachsen.is_holiday(date), true, "{}", date);
+        }
+    }
+
+    #[test]
+    fn test_closures() {
+        let calendar = HolidayCalendar::new(State::BadenWuerttemberg).with_closures(vec![
+            Closure::new(date!(2022:12:24), date!(2023:01:06)).with_yearly(true),
+            Closure::new(date!(2023:05:19), date!(2023:05:19)),
+        ]);
+
+        for date in [
+            date!(2022:12:24),
+            date!(2022:12:28),
+            date!(2023:01:02),
+            date!(2023:01:06),
+            date!(2023:05:19),
+            date!(2024:12:30),
+        ] {
+            assert_eq!(calendar.is_holiday(date), true, "{} should be closed", date);
+        }
+
+        for date in [
+            date!(2022:12:23),
+            date!(2023:01:09),
+            date!(2023:05:22),
+            date!(2024:05:21),
+        ] {
+            assert_eq!(calendar.is_holiday(date), false, "{} should be open", date);
+        }
+    }
+
+    #[test]
+    fn test_available_time_on() {
+        let calendar = HolidayCalendar::default();
+
+        for (date, expected) in [
+            (date!(2024:12:23), working_duration!(06:00)),
+            (date!(2024:12:24), working_duration!(03:00)),
+            (date!(2024:12:25), working_duration!(00:00)),
+            (date!(2024:12:31), working_duration!(03:00)),
+        ] {
+            assert_eq!(
+                calendar.available_time_on(date, working_duration!(06:00)),
+                expected,
+                "{}",
+                date
+            );
+        }
+
+        assert_eq!(calendar.is_holiday(date!(2024:12:24)), false);
+        assert_eq!(date!(2024:12:31).is_workday(&calendar), true);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(State::from_str("BY").unwrap(), State::Bayern);
+        assert_eq!(State::from_str("nw").unwrap(), State::NordrheinWestfalen);
+        assert_eq!(
+            State::from_str("XY").unwrap_err().to_string(),
+            "Unknown state: XY".to_string()
+        );
+    }
+
     #[test]
     fn test_is_easter_sunday() {
         for date in [
@@ -197,10 +828,14 @@ mod tests {
             hint: String,
         }
 
-        for year in 2022..=2025 {
+        for (state, year) in State::ALL
+            .into_iter()
+            .flat_map(|state| (2022..=2025).map(move |year| (state, year)))
+        {
             let res = minreq::get(format!(
-                "https://feiertage-api.de/api/?jahr={}&nur_land=BW",
-                year
+                "https://feiertage-api.de/api/?jahr={}&nur_land={}",
+                year,
+                state.code()
             ))
             .send()
             .expect("Can not reach web api");
@@ -214,10 +849,11 @@ mod tests {
                 }
 
                 assert_eq!(
-                    is_holiday(entry.date),
+                    state.is_holiday(entry.date),
                     true,
-                    "date {} should be a holiday",
-                    entry.date
+                    "date {} should be a holiday in {}",
+                    entry.date,
+                    state.code()
                 );
             }
         }
@@ -1,6 +1,8 @@
+use std::str::FromStr;
 use std::time::Duration;
 
 use derive_more::Display;
+use serde::{de, Deserialize};
 
 use crate::time::{TimeStamp, WorkingDuration};
 use crate::{max, min};
@@ -50,13 +52,57 @@ impl TimeSpan {
     }
 }
 
+impl FromStr for TimeSpan {
+    type Err = anyhow::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let (start, end) = string
+            .split_once('-')
+            .ok_or_else(|| anyhow::anyhow!("expected a time span like `08:00-12:00`"))?;
+
+        let span = Self::new(start.trim().parse()?, end.trim().parse()?);
+        if span.start() > span.end() {
+            return Err(anyhow::anyhow!(
+                "the time span `{}` ends before it starts",
+                span
+            ));
+        }
+
+        Ok(span)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeSpan {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        FromStr::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use pretty_assertions::assert_eq;
 
-    use crate::working_duration;
+    use crate::{time_stamp, working_duration};
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            TimeSpan::from_str("08:00-12:30").unwrap(),
+            TimeSpan::new(time_stamp!(08:00), time_stamp!(12:30))
+        );
+        assert_eq!(
+            TimeSpan::from_str("14:00 - 18:00").unwrap(),
+            TimeSpan::new(time_stamp!(14:00), time_stamp!(18:00))
+        );
+        assert!(TimeSpan::from_str("18:00-14:00").is_err());
+        assert!(TimeSpan::from_str("14:00").is_err());
+    }
 
     #[test]
     fn test_overlapping_duration() {
@@ -1,9 +1,10 @@
 use std::ops::Add;
 use std::str::FromStr;
 
+use derive_more::Display;
 use serde::Deserialize;
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Deserialize, Display)]
 #[serde(try_from = "String")]
 pub enum WeekDay {
     Monday = 1,
@@ -109,7 +109,9 @@ impl FromStr for WorkingDuration {
     type Err = anyhow::Error;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let (hour, minute) = string.split_once(':').unwrap();
+        let (hour, minute) = string.split_once(':').ok_or_else(|| {
+            anyhow::anyhow!("expected a duration like \"40:00\", but got \"{}\"", string)
+        })?;
 
         Ok(Self::new(hour.parse()?, minute.parse()?)?)
     }
@@ -247,4 +249,14 @@ mod tests {
         // essentially the following property has to hold:
         // (a + b) - b = a
     }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "40:00".parse::<WorkingDuration>().unwrap(),
+            working_duration!(40:00)
+        );
+        assert!("40".parse::<WorkingDuration>().is_err());
+        assert!("ab:00".parse::<WorkingDuration>().is_err());
+    }
 }
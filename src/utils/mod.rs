@@ -202,6 +202,50 @@ pub const fn divide_proportionally(numerator: usize, proportion: &mut [usize]) -
     remainder
 }
 
+/// Returns `true` if the `/` separated path matches the pattern.
+///
+/// In a component `*` matches any number of characters and `?` a single one,
+/// a component of `**` matches any number of directories.
+#[must_use]
+pub fn matches_glob(pattern: &str, path: &str) -> bool {
+    fn matches_component(pattern: &[char], name: &[char]) -> bool {
+        match (pattern.first(), name.first()) {
+            (None, None) => true,
+            (Some('*'), _) => {
+                matches_component(&pattern[1..], name)
+                    || (!name.is_empty() && matches_component(pattern, &name[1..]))
+            }
+            (Some('?'), Some(_)) => matches_component(&pattern[1..], &name[1..]),
+            (Some(expected), Some(actual)) if expected == actual => {
+                matches_component(&pattern[1..], &name[1..])
+            }
+            _ => false,
+        }
+    }
+
+    fn matches_components(pattern: &[&str], path: &[&str]) -> bool {
+        match (pattern.first(), path.first()) {
+            (None, None) => true,
+            (Some(&"**"), _) => {
+                matches_components(&pattern[1..], path)
+                    || (!path.is_empty() && matches_components(pattern, &path[1..]))
+            }
+            (Some(expected), Some(actual)) => {
+                matches_component(
+                    &expected.chars().collect::<Vec<_>>(),
+                    &actual.chars().collect::<Vec<_>>(),
+                ) && matches_components(&pattern[1..], &path[1..])
+            }
+            _ => false,
+        }
+    }
+
+    let pattern = pattern.split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+
+    matches_components(&pattern, &path)
+}
+
 pub trait StrExt {
     fn split_exact<const N: usize>(&self, pat: &str) -> [Option<&str>; N];
 }
@@ -242,4 +286,17 @@ mod tests {
             ([393, 590, 590, 590, 295, 0], 2)
         );
     }
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob("*.toml", "2022-10.toml"));
+        assert!(matches_glob("2022-1?.toml", "2022-10.toml"));
+        assert!(!matches_glob("2022-1?.toml", "2022-9.toml"));
+        assert!(!matches_glob("*.toml", "2022/10.toml"));
+        assert!(matches_glob("*/*.toml", "2022/10.toml"));
+        assert!(matches_glob("**/*.toml", "10.toml"));
+        assert!(matches_glob("**/*.toml", "2022/ws/10.toml"));
+        assert!(matches_glob("2022/**", "2022/ws/10.toml"));
+        assert!(!matches_glob("2023/**", "2022/ws/10.toml"));
+    }
 }
@@ -0,0 +1,172 @@
+//! Tests that dynamic entries are only scheduled in the weekly availability
+//! of the contract.
+
+use time_sheet::input::json_input::MonthFile;
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::time::{Date, TimeSpan, WeekDay};
+use time_sheet::{time_stamp, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(
+        &(common::make_global(working_duration!(10:00))
+            + concat!(
+                "[contract.MENSA.scheduler.availability]\n",
+                "tuesday = [\"14:00-17:00\"]\n",
+                "thursday = [\"08:00-09:00\", \"16:00-18:00\"]\n",
+            )),
+    )
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn make_month(scheduler: &str, duration: &str) -> toml_input::Month {
+    toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = 11\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "{}",
+            "\n",
+            "[dynamic.\"filler\"]\n",
+            "duration = \"{}\"\n",
+        ),
+        scheduler, duration
+    ))
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn scheduled_days(month_file: &MonthFile) -> Vec<(WeekDay, TimeSpan)> {
+    month_file
+        .entries()
+        .iter()
+        .map(|entry| {
+            let date = Date::new(month_file.year(), month_file.month(), entry.day()).unwrap();
+            (date.week_day(), entry.time_span())
+        })
+        .collect()
+}
+
+#[test]
+fn test_schedules_in_availability() {
+    let month_file = common::make_month_file(make_global(), make_month("", "10:00"));
+
+    let windows = [
+        (
+            WeekDay::Tuesday,
+            TimeSpan::new(time_stamp!(14:00), time_stamp!(17:00)),
+        ),
+        (
+            WeekDay::Thursday,
+            TimeSpan::new(time_stamp!(08:00), time_stamp!(09:00)),
+        ),
+        (
+            WeekDay::Thursday,
+            TimeSpan::new(time_stamp!(16:00), time_stamp!(18:00)),
+        ),
+    ];
+
+    let days = scheduled_days(&month_file);
+    assert!(!days.is_empty());
+    for (week_day, span) in days {
+        assert!(
+            windows.iter().any(|(day, window)| *day == week_day
+                && window.start() <= span.start()
+                && span.end() <= window.end()),
+            "{} on {} is outside of the availability",
+            span,
+            week_day
+        );
+    }
+}
+
+#[test]
+fn test_month_overrides_availability() {
+    let month_file = common::make_month_file(
+        make_global(),
+        make_month(
+            "[scheduler.availability]\nfriday = [\"10:00-12:00\"]\n",
+            "10:00",
+        ),
+    );
+
+    let days = scheduled_days(&month_file);
+    assert!(!days.is_empty());
+    assert_eq!(
+        days.into_iter()
+            .filter(|(week_day, _)| *week_day != WeekDay::Friday)
+            .collect::<Vec<_>>(),
+        Vec::new()
+    );
+}
+
+#[test]
+fn test_day_with_separate_windows() {
+    // thursday has 3 hours available, but at most 2 hours in one piece
+    let global: Global = toml::from_str(
+        &(common::make_global(working_duration!(10:00))
+            + concat!(
+                "[contract.MENSA.scheduler.availability]\n",
+                "thursday = [\"08:00-09:00\", \"16:00-18:00\"]\n",
+            )),
+    )
+    .expect("toml should be valid");
+    let month_file = common::make_month_file(global, make_month("", "06:00"));
+
+    let days = scheduled_days(&month_file);
+    assert!(!days.is_empty());
+    for (week_day, span) in days {
+        assert_eq!(week_day, WeekDay::Thursday);
+        assert!(
+            span.start() >= time_stamp!(16:00) && span.end() <= time_stamp!(18:00),
+            "{} is not in the longest window",
+            span
+        );
+    }
+}
+
+#[test]
+fn test_fixed_entries_in_availability() {
+    let global: Global = toml::from_str(
+        &(common::make_global(working_duration!(20:00))
+            + concat!(
+                "[contract.MENSA.scheduler]\n",
+                "schedule_with_fixed_entries = true\n",
+                "\n",
+                "[contract.MENSA.scheduler.availability]\n",
+                "tuesday = [\"14:00-18:00\"]\n",
+                "\n",
+                "[repeating.\"meeting\"]\n",
+                "start = \"15:00\"\n",
+                "end = \"16:00\"\n",
+                "repeats_on = [\"Tuesday\"]\n",
+                "repeats_every = \"week\"\n",
+            )),
+    )
+    .expect("toml should be valid");
+
+    let month_file = common::make_month_file(global, make_month("", "10:00"));
+
+    let days = scheduled_days(&month_file);
+    assert!(days
+        .iter()
+        .any(|(_, span)| span.start() != time_stamp!(15:00)));
+    for (week_day, span) in days {
+        assert_eq!(week_day, WeekDay::Tuesday);
+        // the meeting takes 15:00-16:00, so at most the 2 hours after it are free
+        assert!(
+            span == TimeSpan::new(time_stamp!(15:00), time_stamp!(16:00))
+                || span.end() <= time_stamp!(15:00)
+                || span.start() >= time_stamp!(16:00),
+            "{} overlaps with the meeting",
+            span
+        );
+    }
+}
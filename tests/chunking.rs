@@ -0,0 +1,84 @@
+//! Tests that dynamic entries are split into chunks with a minimum length
+//! and that the chunks are rounded.
+
+use time_sheet::input::json_input::MonthFile;
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::time::WorkingDuration;
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(
+        &(common::make_global(working_duration!(10:07))
+            + concat!(
+                "[contract.MENSA.scheduler]\n",
+                "min_chunk = \"00:30\"\n",
+                "rounding = \"00:15\"\n",
+            )),
+    )
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn make_month(dynamic: &str) -> toml_input::Month {
+    toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = 11\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "[dynamic.\"Korrektur\"]\n",
+            "duration = \"10:07\"\n",
+            "{}",
+        ),
+        dynamic
+    ))
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn durations(month_file: &MonthFile) -> Vec<WorkingDuration> {
+    month_file
+        .entries()
+        .iter()
+        .map(|entry| entry.work_duration())
+        .collect()
+}
+
+fn assert_chunks(durations: &[WorkingDuration], rounding: u16) {
+    assert_eq!(
+        durations.iter().copied().sum::<WorkingDuration>(),
+        working_duration!(10:07)
+    );
+    assert!(durations
+        .iter()
+        .all(|duration| *duration >= working_duration!(00:30)));
+    // only the last chunk contains the rest of the rounding:
+    assert_eq!(
+        durations
+            .iter()
+            .filter(|duration| duration.as_mins() % rounding != 0)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_contract_chunking() {
+    let month_file = common::make_month_file(make_global(), make_month(""));
+
+    assert_chunks(&durations(&month_file), 15);
+}
+
+#[test]
+fn test_dynamic_entry_overrides_chunking() {
+    let month_file = common::make_month_file(make_global(), make_month("rounding = \"00:20\"\n"));
+
+    assert_chunks(&durations(&month_file), 20);
+}
+
@@ -60,6 +60,7 @@ pub fn get_proportions(json_month_file: &MonthFile) -> IndexMap<&str, WorkingDur
 }
 
 #[must_use]
+#[allow(dead_code)]
 pub fn make_month_file(global: toml_input::Global, month: toml_input::Month) -> MonthFile {
     let config = Config::try_from_toml(month, global)
         .expect("config should be valid")
@@ -0,0 +1,124 @@
+//! Tests that occurrences of repeating events, which do not fit on their day,
+//! are reported together with the reason why they have been left out.
+
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::{Config, DropReason};
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[test]
+fn test_dropped_repeating_entries() {
+    let global: Global = toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"Tutorium\"]\n",
+            "start = \"08:00\"\n",
+            "end = \"12:00\"\n",
+            "repeats_on = [\"Tuesday\"]\n",
+            "repeats_every = \"week\"\n",
+        ),
+        common::make_global(working_duration!(40:00))
+    ))
+    .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[absence.8]\n",
+        "start = \"10:00\"\n",
+        "end = \"11:00\"\n",
+        "\n",
+        "[entries.15]\n",
+        "action = \"Besprechung\"\n",
+        "start = \"11:00\"\n",
+        "end = \"12:00\"\n",
+        "\n",
+        "[entries.22]\n",
+        "action = \"Sitzung\"\n",
+        "start = \"13:00\"\n",
+        "end = \"18:00\"\n",
+        "\n",
+        "[sick.29]\n",
+    ))
+    .expect("toml should be valid");
+
+    let config = Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build");
+
+    assert_eq!(
+        config
+            .dropped_entries()
+            .iter()
+            .map(|dropped| (
+                dropped.entry().action(),
+                dropped.date().day(),
+                dropped.reason().clone()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("Tutorium", 8, DropReason::AbsenceOverlap),
+            (
+                "Tutorium",
+                15,
+                DropReason::EntryOverlap {
+                    action: "Besprechung".to_string()
+                }
+            ),
+            (
+                "Tutorium",
+                22,
+                DropReason::DailyLimitExceeded {
+                    maximum: working_duration!(08:00)
+                }
+            ),
+            ("Tutorium", 29, DropReason::BlockedDay),
+        ]
+    );
+
+    // none of them end up in the time sheet
+    assert!(config
+        .month()
+        .to_month_file()
+        .entries()
+        .iter()
+        .all(|entry| entry.action() != "Tutorium"));
+}
+
+#[test]
+fn test_nothing_dropped() {
+    let global: Global = toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"Tutorium\"]\n",
+            "start = \"08:00\"\n",
+            "end = \"12:00\"\n",
+            "repeats_on = [\"Tuesday\"]\n",
+            "repeats_every = \"week\"\n",
+        ),
+        common::make_global(working_duration!(40:00))
+    ))
+    .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+    ))
+    .expect("toml should be valid");
+
+    let config = Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build");
+
+    assert_eq!(config.dropped_entries(), &[]);
+}
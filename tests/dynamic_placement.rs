@@ -0,0 +1,135 @@
+//! Tests that dynamic entries are placed in free time slots, without
+//! overlapping other entries.
+
+use time_sheet::input::json_input::{self, Entry};
+use time_sheet::input::scheduler::SchedulerOptions;
+use time_sheet::input::{toml_input, EntrySource, Month, UnscheduledWork};
+use time_sheet::time::TimeSpan;
+use time_sheet::{time_stamp, working_duration};
+
+use pretty_assertions::assert_eq;
+
+#[must_use]
+fn make_month(working_hours: TimeSpan) -> Month {
+    let input: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[absence.01-29]\n",
+        "start = \"00:00\"\n",
+        "end = \"23:59\"\n",
+        "\n",
+        "[entries.30]\n",
+        "action = \"meeting\"\n",
+        "start = \"09:00\"\n",
+        "end = \"11:00\"\n",
+        "\n",
+        "[dynamic.\"task a\"]\n",
+        "duration = \"01:00\"\n",
+    ))
+    .expect("toml should be valid");
+
+    let mut options = SchedulerOptions::default();
+    options.should_schedule_with_fixed_entries = true;
+    options.working_hours = working_hours;
+
+    Month::new(
+        input.general().month(),
+        input.general().year(),
+        input.transfer().unwrap_or_default(),
+        input.entries().map(json_input::Entry::from).collect(),
+        input.dynamic_entries().cloned().collect(),
+        Some(working_duration!(03:00)),
+        input.absences().collect(),
+        options,
+    )
+}
+
+#[test]
+fn test_dynamic_entry_is_placed_after_fixed_entry() {
+    let month = make_month(TimeSpan::new(time_stamp!(06:00), time_stamp!(22:00)));
+
+    assert_eq!(
+        month.scheduled_entries(),
+        vec![
+            (
+                EntrySource::Fixed,
+                Entry::new("meeting", 30, time_stamp!(09:00), time_stamp!(11:00), None)
+            ),
+            (
+                EntrySource::Dynamic,
+                Entry::new("task a", 30, time_stamp!(11:00), time_stamp!(12:00), None)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_dynamic_entry_is_placed_before_fixed_entry() {
+    let month = make_month(TimeSpan::new(time_stamp!(08:00), time_stamp!(11:30)));
+
+    assert_eq!(
+        month.scheduled_entries(),
+        vec![
+            (
+                EntrySource::Dynamic,
+                Entry::new("task a", 30, time_stamp!(08:00), time_stamp!(09:00), None)
+            ),
+            (
+                EntrySource::Fixed,
+                Entry::new("meeting", 30, time_stamp!(09:00), time_stamp!(11:00), None)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_split_into_free_slots() {
+    let month = make_month(TimeSpan::new(time_stamp!(08:30), time_stamp!(11:30)));
+
+    // there is no free slot of an hour, but two of half an hour
+    assert_eq!(
+        month.scheduled_entries(),
+        vec![
+            (
+                EntrySource::Dynamic,
+                Entry::new("task a", 30, time_stamp!(08:30), time_stamp!(09:00), None)
+            ),
+            (
+                EntrySource::Fixed,
+                Entry::new("meeting", 30, time_stamp!(09:00), time_stamp!(11:00), None)
+            ),
+            (
+                EntrySource::Dynamic,
+                Entry::new("task a", 30, time_stamp!(11:00), time_stamp!(11:30), None)
+            ),
+        ]
+    );
+    assert_eq!(month.unscheduled_work(), vec![]);
+}
+
+#[test]
+fn test_no_free_slot_is_unscheduled() {
+    let month = make_month(TimeSpan::new(time_stamp!(09:00), time_stamp!(11:30)));
+
+    assert_eq!(
+        month.scheduled_entries(),
+        vec![
+            (
+                EntrySource::Fixed,
+                Entry::new("meeting", 30, time_stamp!(09:00), time_stamp!(11:00), None)
+            ),
+            (
+                EntrySource::Dynamic,
+                Entry::new("task a", 30, time_stamp!(11:00), time_stamp!(11:30), None)
+            ),
+        ]
+    );
+    assert_eq!(
+        month.unscheduled_work(),
+        vec![UnscheduledWork::new("task a", working_duration!(00:30))]
+    );
+}
@@ -0,0 +1,47 @@
+//! Tests that at most half of a day is scheduled on christmas eve and new year's eve.
+
+use time_sheet::input::toml_input;
+use time_sheet::time::WorkingDuration;
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[test]
+fn test_half_days() {
+    // more work than fits into the month, so every day is filled up to the daily limit
+    let global = toml::from_str(
+        &(common::make_global(working_duration!(60:00))
+            + "[contract.MENSA.scheduler]\ndaily_limit = \"02:00\"\n"),
+    )
+    .expect("toml should be valid");
+    let month: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 12\n",
+        "year = 2024\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[dynamic.\"filler\"]\n",
+        "duration = \"60:00\"\n",
+    ))
+    .expect("toml should be valid");
+
+    let month_file = common::make_month_file(global, month);
+
+    let worked_on = |day: usize| -> WorkingDuration {
+        month_file
+            .entries()
+            .iter()
+            .filter(|entry| entry.day() == day)
+            .map(|entry| entry.work_duration())
+            .sum()
+    };
+
+    // the days before are full days
+    assert_eq!(worked_on(23), working_duration!(02:00));
+    assert_eq!(worked_on(30), working_duration!(02:00));
+
+    assert_eq!(worked_on(24), working_duration!(01:00));
+    assert_eq!(worked_on(31), working_duration!(01:00));
+}
@@ -0,0 +1,106 @@
+//! Tests that a vacation block like `[holiday.24-28]` is spread across the
+//! workdays of the range and that nothing else is scheduled on those days.
+
+use time_sheet::input::json_input::Entry;
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::time::WorkingDuration;
+use time_sheet::{time_stamp, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"meeting\"]\n",
+            "start = \"14:00\"\n",
+            "end = \"15:00\"\n",
+            "repeats_on = [\"Thursday\", \"Friday\"]\n",
+            "repeats_every = \"week\"\n",
+            "department = \"MENSA\"\n",
+        ),
+        common::make_global(working_duration!(40:00))
+    ))
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn make_month(holiday: &str) -> toml_input::Month {
+    toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = 11\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "[dynamic.\"filler\"]\n",
+            "duration = \"40:00\"\n",
+            "\n",
+            "{}",
+        ),
+        holiday
+    ))
+    .expect("toml should be valid")
+}
+
+#[test]
+fn test_holiday_block_is_spread_across_workdays() {
+    let month_file = common::make_month_file(
+        make_global(),
+        make_month("[holiday.24-28]\nstart = \"10:00\"\n"),
+    );
+
+    let in_block = month_file
+        .entries()
+        .iter()
+        .filter(|entry| (24..=28).contains(&entry.day()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // the 03:06 of vacation are spread across the workdays, the sunday is skipped
+    assert_eq!(
+        in_block,
+        vec![
+            Entry::new_vacation("Urlaub", 24, time_stamp!(10:00), time_stamp!(10:47)),
+            Entry::new_vacation("Urlaub", 25, time_stamp!(10:00), time_stamp!(10:47)),
+            Entry::new_vacation("Urlaub", 26, time_stamp!(10:00), time_stamp!(10:46)),
+            Entry::new_vacation("Urlaub", 28, time_stamp!(10:00), time_stamp!(10:46)),
+        ]
+    );
+
+    // the meetings outside of the block are still there
+    assert!(month_file
+        .entries()
+        .iter()
+        .any(|entry| entry.day() == 17 && entry.action() == "meeting"));
+}
+
+#[test]
+fn test_multiple_holiday_blocks() {
+    let month_file = common::make_month_file(
+        make_global(),
+        make_month(concat!(
+            "[holiday.7]\n",
+            "start = \"08:00\"\n",
+            "\n",
+            "[holiday.21-22]\n",
+            "start = \"08:00\"\n",
+        )),
+    );
+
+    let vacation_on = |day: usize| -> WorkingDuration {
+        month_file
+            .entries()
+            .iter()
+            .filter(|entry| entry.day() == day && entry.is_vacation())
+            .map(|entry| entry.work_duration())
+            .sum()
+    };
+
+    assert_eq!(vacation_on(7), working_duration!(03:06));
+    assert_eq!(vacation_on(21), working_duration!(01:33));
+    assert_eq!(vacation_on(22), working_duration!(01:33));
+}
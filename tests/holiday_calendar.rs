@@ -0,0 +1,99 @@
+//! Tests that the holidays of the state configured in the global file are
+//! used for the repeating events and the dynamic entries, together with the
+//! closure days of the institution.
+
+use time_sheet::input::json_input::MonthFile;
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global(config: &str) -> Global {
+    toml::from_str(&format!(
+        concat!(
+            "[config]\n",
+            "{}",
+            "\n",
+            "{}",
+            "[repeating.\"Besprechung\"]\n",
+            "start = \"10:00\"\n",
+            "end = \"11:00\"\n",
+            "repeats_on = [\"Wednesday\"]\n",
+            "repeats_every = \"week\"\n",
+            "department = \"MENSA\"\n",
+        ),
+        config,
+        common::make_global(working_duration!(10:00))
+    ))
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn make_month() -> toml_input::Month {
+    toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[dynamic.\"filler\"]\n",
+        "duration = \"06:00\"\n",
+    ))
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn days_of(month_file: &MonthFile, action: &str) -> Vec<usize> {
+    month_file
+        .entries()
+        .iter()
+        .filter(|entry| entry.action() == action)
+        .map(|entry| entry.day())
+        .collect()
+}
+
+#[test]
+fn test_default_calendar() {
+    let month_file = common::make_month_file(make_global(""), make_month());
+
+    // all wednesdays of november 2022
+    assert_eq!(days_of(&month_file, "Besprechung"), vec![2, 9, 16, 23, 30]);
+    // allerheiligen is a holiday in baden-württemberg
+    assert!(!days_of(&month_file, "filler").contains(&1));
+}
+
+#[test]
+fn test_state_calendar() {
+    let month_file = common::make_month_file(make_global("holidays = \"SN\"\n"), make_month());
+
+    // the 16th is buß- und bettag in sachsen
+    assert_eq!(days_of(&month_file, "Besprechung"), vec![2, 9, 23, 30]);
+    assert!(!days_of(&month_file, "filler").contains(&16));
+}
+
+#[test]
+fn test_closures() {
+    let month_file = common::make_month_file(
+        make_global(concat!(
+            "holidays = \"BW\"\n",
+            "\n",
+            "[[closure]]\n",
+            "start = \"2022-11-09\"\n",
+            "\n",
+            "[[closure]]\n",
+            "name = \"Betriebsausflug\"\n",
+            "start = \"2015-11-21\"\n",
+            "end = \"2015-11-25\"\n",
+            "yearly = true\n",
+        )),
+        make_month(),
+    );
+
+    assert_eq!(days_of(&month_file, "Besprechung"), vec![2, 16, 30]);
+    assert!(days_of(&month_file, "filler")
+        .into_iter()
+        .all(|day| day != 9 && !(21..=25).contains(&day)));
+}
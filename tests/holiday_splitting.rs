@@ -0,0 +1,81 @@
+//! Tests that a holiday is split up, when it does not fit into a single
+//! block on the requested day.
+
+use time_sheet::input::json_input::{Entry, MonthFile};
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::time::{Month, Year};
+use time_sheet::{time_stamp, transfer, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_month(fixed_start: &str, fixed_end: &str) -> toml_input::Month {
+    toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = 11\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "[scheduler]\n",
+            "working_hours = \"08:00-18:00\"\n",
+            "\n",
+            "[entries.23]\n",
+            "action = \"meeting\"\n",
+            "start = \"{start}\"\n",
+            "end = \"{end}\"\n",
+            "\n",
+            "[holiday]\n",
+            "day = 23\n",
+            "start = \"10:00\"\n",
+        ),
+        start = fixed_start,
+        end = fixed_end,
+    ))
+    .expect("toml should be valid")
+}
+
+#[test]
+fn test_holiday_is_split_around_entry() {
+    // with 85 hours a month, the holiday takes 06:35, which does not fit
+    // after the meeting, because the working hours end at 18:00
+    let global: Global = toml::from_str(&common::make_global(working_duration!(85:00)))
+        .expect("toml should be valid");
+
+    assert_eq!(
+        common::make_month_file(global, make_month("12:00", "13:00")),
+        MonthFile::new(
+            Year::new(2022),
+            Month::November,
+            transfer!(+00:00),
+            vec![
+                Entry::new_vacation("Urlaub", 23, time_stamp!(10:00), time_stamp!(12:00)),
+                Entry::new("meeting", 23, time_stamp!(12:00), time_stamp!(13:00), None),
+                Entry::new_vacation("Urlaub", 23, time_stamp!(13:00), time_stamp!(17:35)),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_holiday_is_split_across_days() {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(85:00)))
+        .expect("toml should be valid");
+
+    // only three hours can be worked on the 23rd, the rest is moved to the next workday
+    assert_eq!(
+        common::make_month_file(global, make_month("09:00", "14:00")),
+        MonthFile::new(
+            Year::new(2022),
+            Month::November,
+            transfer!(+00:00),
+            vec![
+                Entry::new("meeting", 23, time_stamp!(09:00), time_stamp!(14:00), None),
+                Entry::new_vacation("Urlaub", 23, time_stamp!(14:00), time_stamp!(17:00)),
+                Entry::new_vacation("Urlaub", 24, time_stamp!(10:00), time_stamp!(13:35)),
+            ]
+        )
+    );
+}
@@ -0,0 +1,101 @@
+//! Tests that the answers of the `init` command result in a valid global file.
+
+use std::io::Cursor;
+
+use time_sheet::date;
+use time_sheet::input::GlobalScaffold;
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_init_global() {
+    let answers = concat!(
+        "John Smith\n",
+        "1234567\n",
+        "\n",
+        "MENSA\n",
+        // an invalid working time is asked again
+        "40\n",
+        "40:00\n",
+        "gf\n",
+        "12.50\n",
+        "2022-10-01\n",
+        "2023-09-30\n",
+        "n\n",
+    );
+
+    let mut output = Vec::new();
+    let scaffold = GlobalScaffold::ask(&mut Cursor::new(answers), &mut output).unwrap();
+    let global = scaffold.to_global().unwrap();
+
+    assert_eq!(global.about().name(), "John Smith");
+    assert_eq!(global.about().staff_id(), 1234567);
+    assert!(global.about().signature().is_none());
+    assert!(global.mail().is_none());
+
+    let contract = global.contract("MENSA").expect("contract should exist");
+    assert_eq!(
+        contract.expected_working_duration(),
+        working_duration!(40:00)
+    );
+    assert_eq!(contract.working_area().to_string(), "gf");
+    assert_eq!(contract.wage(), Some(12.5));
+    assert_eq!(contract.start_date(), date!(2022:10:01));
+    assert_eq!(contract.end_date(), date!(2023:09:30));
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(
+        output
+            .matches("Working time per month (like \"40:00\"): ")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_init_with_mail() {
+    let answers = concat!(
+        "Erika \"Eri\" Mustermann\n",
+        "7654321\n",
+        "signature.png\n",
+        "Institut für Informatik\n",
+        "20:00\n",
+        // the area and the end date must be valid
+        "xy\n",
+        "ub\n",
+        "\n",
+        "2023-01-01\n",
+        "2022-12-31\n",
+        "2023-12-31\n",
+        "y\n",
+        "not an email\n",
+        "erika@example.com\n",
+        "smtp.example.com\n",
+        "erika\n",
+        "yes\n",
+    );
+
+    let scaffold = GlobalScaffold::ask(&mut Cursor::new(answers), &mut Vec::new()).unwrap();
+    let global = scaffold.to_global().unwrap();
+
+    assert_eq!(global.about().name(), "Erika \"Eri\" Mustermann");
+    assert!(global.about().signature().is_some());
+    assert!(global.mail().is_some());
+    // the password is not asked for, so it is not shown in the terminal:
+    assert!(scaffold
+        .to_string()
+        .contains("# password = \"your password\"\n"));
+
+    let contract = global
+        .contract("Institut für Informatik")
+        .expect("contract should exist");
+    assert_eq!(contract.working_area().to_string(), "ub");
+    assert_eq!(contract.wage(), None);
+    assert_eq!(contract.end_date(), date!(2023:12:31));
+}
+
+#[test]
+fn test_init_missing_answer() {
+    assert!(GlobalScaffold::ask(&mut Cursor::new("John Smith\n"), &mut Vec::new()).is_err());
+}
@@ -0,0 +1,336 @@
+//! Tests that the natively generated latex file is identical to the one
+//! generated by the TimeSheetGenerator.
+//!
+//! The expected files in `tests/golden/` are the output of the embedded
+//! `TimeSheetGenerator.jar` for the inputs of the other tests. After the jar
+//! or a fixture changed, they can be generated again with (requires java and
+//! access to feiertage-api.de, where the jar looks up the holidays):
+//!
+//! ```text
+//! cargo test --test latex_generation -- --ignored
+//! ```
+
+use std::fs;
+use std::process::Command;
+
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::Config;
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+/// Makes the global and month file of a test.
+type Fixture = fn() -> (Global, toml_input::Month);
+
+#[must_use]
+fn make_config(global: Global, month: toml_input::Month) -> Config {
+    Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build")
+}
+
+#[must_use]
+fn generate_latex(global: Global, month: toml_input::Month) -> String {
+    time_sheet::generate_latex(&make_config(global, month)).expect("latex should be generated")
+}
+
+#[must_use]
+fn generate_latex_with_jar(global: Global, month: toml_input::Month) -> String {
+    let config = make_config(global, month);
+    let dir = tempfile::tempdir().expect("should be able to make a temporary directory");
+    let global_path = dir.path().join("global.json");
+    let month_path = dir.path().join("month.json");
+    let latex_path = dir.path().join("output.tex");
+
+    config.write_global_json(&global_path).unwrap();
+    config.write_month_json(&month_path).unwrap();
+
+    let output = Command::new("java")
+        .arg("-jar")
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/resources/TimeSheetGenerator.jar"
+        ))
+        .arg("--file")
+        .args([&global_path, &month_path, &latex_path])
+        .output()
+        .expect("java should be installed");
+    assert!(
+        output.status.success() && output.stdout.is_empty(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    fs::read_to_string(latex_path).expect("the jar should have written the latex file")
+}
+
+/// The inputs of `tests/explicit_values_dynamic.rs`.
+fn explicit_values_dynamic() -> (Global, toml_input::Month) {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(40:00)))
+        .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 8\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[dynamic.\"task a\"]\n",
+        "flex = 1\n",
+        "start = \"13:00\"\n",
+        "\n",
+        "[dynamic.\"task b\"]\n",
+        "flex = 1\n",
+        "start = \"14:00\"\n",
+        "pause = \"02:00\"\n",
+        "\n",
+    ))
+    .expect("toml should be valid");
+
+    (global, month)
+}
+
+/// The inputs of `test_only_flex_divides_exact` in `tests/flex_distribution.rs`.
+fn flex_distribution() -> (Global, toml_input::Month) {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(40:00)))
+        .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 8\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[dynamic.\"task a\"]\n",
+        "flex = 1\n",
+        "\n",
+        "[dynamic.\"task b\"]\n",
+        "flex = 2\n",
+        "\n",
+        "[dynamic.\"task c\"]\n",
+        "flex = 3\n",
+        "\n",
+        "[dynamic.\"task d\"]\n",
+        "flex = 4\n",
+    ))
+    .expect("toml should be valid");
+
+    (global, month)
+}
+
+/// The inputs of `tests/proportional_distribution.rs`.
+fn proportional_distribution() -> (Global, toml_input::Month) {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(40:00)))
+        .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 8\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "strategy = \"proportional\"\n",
+        "\n",
+        "[dynamic.\"task a\"]\n",
+        "duration = \"16:00\"\n",
+        "\n",
+        "[dynamic.\"task b\"]\n",
+        "duration = \"32:00\"\n",
+        "\n",
+        "[dynamic.\"task c\"]\n",
+        "duration = \"16:00\"\n",
+        "\n",
+        "[dynamic.\"task d\"]\n",
+        "duration = \"16:00\"\n",
+    ))
+    .expect("toml should be valid");
+
+    (global, month)
+}
+
+/// The inputs of `tests/repeating_dynamic.rs`.
+fn repeating_dynamic() -> (Global, toml_input::Month) {
+    let global: Global = toml::from_str(
+        &(common::make_global(working_duration!(40:00))
+            + concat!(
+                "[repeating.\"regular work\"]\n",
+                "start = \"08:00\"\n",
+                "repeats_on = [\"Tuesday\"]\n",
+                "repeats_every = \"week\"\n",
+                "duration = \"03:00\"\n",
+            )),
+    )
+    .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 8\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[dynamic.\"task a\"]\n",
+        "flex = 1\n",
+        "\n",
+        "[dynamic.\"task b\"]\n",
+        "flex = 1\n",
+        "\n",
+    ))
+    .expect("toml should be valid");
+
+    (global, month)
+}
+
+/// The inputs of `tests/repeating_events_and_absence.rs`.
+fn repeating_events_and_absence() -> (Global, toml_input::Month) {
+    let global: Global = toml::from_str(
+        &(common::make_global(working_duration!(40:00))
+            + concat!(
+                "[repeating.\"regular work\"]\n",
+                "start = \"08:00\"\n",
+                "end = \"12:00\"\n",
+                "repeats_on = [\"Tuesday\", \"Friday\"]\n",
+                "repeats_every = \"week\"\n"
+            )),
+    )
+    .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[absence.08]\n",
+        "start = \"08:00\"\n",
+        "end = \"12:00\"\n",
+        "\n",
+        "[dynamic.\"filler\"]\n",
+        "duration = \"08:00\"\n"
+    ))
+    .expect("toml should be valid");
+
+    (global, month)
+}
+
+/// The inputs of `test_transfer_previous_and_next_month` in `tests/transfer_time.rs`.
+fn transfer_time() -> (Global, toml_input::Month) {
+    let global: Global = toml::from_str(
+        &(common::make_global(working_duration!(15:00))
+            + concat!(
+                "[repeating.\"regular work\"]\n",
+                "start = \"08:00\"\n",
+                "end = \"10:00\"\n",
+                "repeats_on = [\"Tuesday\"]\n",
+                "repeats_every = \"week\"\n"
+            )),
+    )
+    .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[transfer]\n",
+        "prev = \"02:00\"\n",
+        "next = \"05:00\"\n",
+        "\n",
+        "[dynamic.\"filler\"]\n",
+        "duration = \"20:00\"\n"
+    ))
+    .expect("toml should be valid");
+
+    (global, month)
+}
+
+#[test]
+fn test_explicit_values_dynamic() {
+    let (global, month) = explicit_values_dynamic();
+
+    assert_eq!(
+        generate_latex(global, month),
+        include_str!("golden/explicit_values_dynamic.tex")
+    );
+}
+
+#[test]
+fn test_flex_distribution() {
+    let (global, month) = flex_distribution();
+
+    assert_eq!(
+        generate_latex(global, month),
+        include_str!("golden/flex_distribution.tex")
+    );
+}
+
+#[test]
+fn test_proportional_distribution() {
+    let (global, month) = proportional_distribution();
+
+    assert_eq!(
+        generate_latex(global, month),
+        include_str!("golden/proportional_distribution.tex")
+    );
+}
+
+#[test]
+fn test_repeating_dynamic() {
+    let (global, month) = repeating_dynamic();
+
+    assert_eq!(
+        generate_latex(global, month),
+        include_str!("golden/repeating_dynamic.tex")
+    );
+}
+
+#[test]
+fn test_repeating_events_and_absence() {
+    let (global, month) = repeating_events_and_absence();
+
+    assert_eq!(
+        generate_latex(global, month),
+        include_str!("golden/repeating_events_and_absence.tex")
+    );
+}
+
+#[test]
+fn test_transfer_time() {
+    let (global, month) = transfer_time();
+
+    assert_eq!(
+        generate_latex(global, month),
+        include_str!("golden/transfer_time.tex")
+    );
+}
+
+/// Generates the expected files in `tests/golden/` with the jar.
+#[test]
+#[ignore = "requires java and network access, overwrites the golden files"]
+fn generate_golden_files() {
+    let fixtures: [(&str, Fixture); 6] = [
+        ("explicit_values_dynamic", explicit_values_dynamic),
+        ("flex_distribution", flex_distribution),
+        ("proportional_distribution", proportional_distribution),
+        ("repeating_dynamic", repeating_dynamic),
+        ("repeating_events_and_absence", repeating_events_and_absence),
+        ("transfer_time", transfer_time),
+    ];
+
+    for (name, fixture) in fixtures {
+        let (global, month) = fixture();
+        fs::write(
+            format!("{}/tests/golden/{}.tex", env!("CARGO_MANIFEST_DIR"), name),
+            generate_latex_with_jar(global, month),
+        )
+        .unwrap();
+    }
+}
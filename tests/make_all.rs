@@ -0,0 +1,90 @@
+//! Tests that the month files of a workspace are found for `make --all`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use time_sheet::input::toml_input;
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+const MONTH: &str = "[general]\nmonth = 11\nyear = 2022\ndepartment = \"MENSA\"\n";
+
+#[must_use]
+fn make_workspace() -> tempfile::TempDir {
+    let workspace = tempfile::tempdir().unwrap();
+    let path = workspace.path();
+
+    fs::create_dir_all(path.join("2022")).unwrap();
+    fs::create_dir_all(path.join(".git")).unwrap();
+
+    fs::write(
+        path.join("global.toml"),
+        common::make_global(working_duration!(10:00)),
+    )
+    .unwrap();
+    fs::write(path.join("2022").join("10.toml"), MONTH).unwrap();
+    fs::write(path.join("2022").join("11.toml"), MONTH).unwrap();
+    // a broken month is still found, the error is reported when it is made
+    fs::write(path.join("2022").join("12.toml"), "[general]\nmonth = \n").unwrap();
+    fs::write(path.join("2023-01.toml"), MONTH).unwrap();
+    fs::write(path.join("settings.toml"), "[format]\nwidth = 2\n").unwrap();
+    fs::write(path.join("notes.txt"), MONTH).unwrap();
+    fs::write(path.join(".git").join("month.toml"), MONTH).unwrap();
+
+    workspace
+}
+
+#[must_use]
+fn relative(workspace: &Path, files: Vec<PathBuf>) -> Vec<String> {
+    files
+        .into_iter()
+        .map(|path| {
+            path.strip_prefix(workspace)
+                .unwrap()
+                .iter()
+                .map(|component| component.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_find_in_directory() {
+    let workspace = make_workspace();
+    let path = workspace.path();
+
+    let files = toml_input::Month::find_files(path, path.join("global.toml")).unwrap();
+
+    assert_eq!(
+        relative(path, files),
+        vec![
+            "2022/10.toml",
+            "2022/11.toml",
+            "2022/12.toml",
+            "2023-01.toml"
+        ]
+    );
+}
+
+#[test]
+fn test_find_with_glob() {
+    let workspace = make_workspace();
+    let path = workspace.path();
+
+    let files =
+        toml_input::Month::find_files(path.join("2022").join("1?.toml"), path.join("global.toml"))
+            .unwrap();
+    assert_eq!(
+        relative(path, files),
+        vec!["2022/10.toml", "2022/11.toml", "2022/12.toml"]
+    );
+
+    let files =
+        toml_input::Month::find_files(path.join("**").join("*-*.toml"), path.join("global.toml"))
+            .unwrap();
+    assert_eq!(relative(path, files), vec!["2023-01.toml"]);
+}
@@ -0,0 +1,114 @@
+//! Tests that the month file of the next month is made from the previous one.
+
+use time_sheet::input::scheduler::Strategy;
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::MonthScaffold;
+use time_sheet::time::{Month, Year};
+use time_sheet::{date, transfer, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[[closure]]\n",
+            "name = \"Weihnachtsschließung\"\n",
+            "start = \"2022-12-24\"\n",
+            "end = \"2023-01-06\"\n",
+            "yearly = true\n",
+        ),
+        common::make_global(working_duration!(10:00))
+    ))
+    .expect("toml should be valid")
+}
+
+#[test]
+fn test_next_month() {
+    let previous: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "strategy = \"proportional\"\n",
+        "\n",
+        "[general.signature]\n",
+        "date = \"2022-11-30\"\n",
+        "\n",
+        "[entries.2]\n",
+        "action = \"Tutorium\"\n",
+        "start = \"08:00\"\n",
+        "end = \"14:00\"\n",
+        "\n",
+        "[entries.3]\n",
+        "action = \"Tutorium\"\n",
+        "start = \"08:00\"\n",
+        "end = \"14:00\"\n",
+    ))
+    .expect("toml should be valid");
+
+    let scaffold = MonthScaffold::from_previous(&make_global(), &previous).unwrap();
+    let month = scaffold.to_month().unwrap();
+
+    assert_eq!(month.general().year(), Year::new(2022));
+    assert_eq!(month.general().month(), Month::December);
+    assert_eq!(month.general().department(), "MENSA");
+    assert_eq!(month.general().strategy(), Some(Strategy::Proportional));
+    // the days from the 24th on are closed
+    assert_eq!(
+        month
+            .general()
+            .signature()
+            .map(|signature| signature.date()),
+        Some(date!(2022:12:23))
+    );
+    // 12:00 have been worked in november, but only 10:00 were required
+    assert_eq!(month.transfer(), Some(transfer!(-02:00)));
+    assert_eq!(
+        month
+            .absences()
+            .map(|(date, _)| date.day())
+            .collect::<Vec<_>>(),
+        (24..=31).collect::<Vec<_>>()
+    );
+
+    assert!(scaffold
+        .to_string()
+        .contains("# Weihnachtsschließung\n[absence.24-31]\n"));
+}
+
+#[test]
+fn test_next_year() {
+    let previous: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 12\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+    ))
+    .expect("toml should be valid");
+
+    let scaffold = MonthScaffold::from_previous(&make_global(), &previous).unwrap();
+    let month = scaffold.to_month().unwrap();
+
+    assert_eq!(month.general().year(), Year::new(2023));
+    assert_eq!(month.general().month(), Month::January);
+    assert_eq!(month.general().strategy(), None);
+    assert_eq!(
+        month
+            .general()
+            .signature()
+            .map(|signature| signature.date()),
+        Some(date!(2023:01:31))
+    );
+    assert_eq!(month.transfer(), Some(transfer!(+00:00)));
+    assert_eq!(
+        month
+            .absences()
+            .map(|(date, _)| date.day())
+            .collect::<Vec<_>>(),
+        (1..=6).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,138 @@
+//! Tests that the transfers between consecutive months are checked and
+//! that missing transfers are filled in.
+
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::{OvertimeLedger, OvertimeProblem, Transfer};
+use time_sheet::time::{Month, Year};
+use time_sheet::{transfer, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(&common::make_global(working_duration!(10:00))).expect("toml should be valid")
+}
+
+/// Makes a month of 2022 with entries of four hours on the `days` and one
+/// of `extra` hours on the day after.
+#[must_use]
+fn make_month(
+    month: usize,
+    transfer: Option<Transfer>,
+    days: &[usize],
+    extra: usize,
+) -> toml_input::Month {
+    let mut result = format!(
+        concat!(
+            "[general]\n",
+            "month = {}\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+        ),
+        month
+    );
+
+    if let Some(transfer) = transfer {
+        result += &format!(
+            "[transfer]\nprevious_month = \"{}\"\nnext_month = \"{}\"\n\n",
+            transfer.previous(),
+            transfer.next()
+        );
+    }
+
+    for day in days {
+        result += &format!(
+            "[entries.{}]\naction = \"Tutorium\"\nstart = \"08:00\"\nend = \"12:00\"\n\n",
+            day
+        );
+    }
+
+    if extra > 0 {
+        result += &format!(
+            "[entries.{}]\naction = \"Tutorium\"\nstart = \"08:00\"\nend = \"{:02}:00\"\n\n",
+            days.last().unwrap() + 1,
+            8 + extra
+        );
+    }
+
+    toml::from_str(&result).expect("toml should be valid")
+}
+
+#[test]
+fn test_consecutive_months() {
+    let months = vec![
+        // 12:00 in october, 2:00 more than expected
+        make_month(10, Some(transfer!(+02:00)), &[4, 5, 6], 0),
+        // 8:00 in november, the 2:00 from october are used up
+        make_month(11, Some(transfer!(-02:00)), &[2, 3], 0),
+        // 11:00 in december, without a transfer
+        make_month(12, None, &[5, 6], 3),
+    ];
+
+    let ledger = OvertimeLedger::from_months(&make_global(), "MENSA", months).unwrap();
+
+    assert_eq!(
+        ledger.transfer(Year::new(2022), Month::December),
+        Some(transfer!(+01:00))
+    );
+    assert_eq!(
+        ledger.filled_transfers().collect::<Vec<_>>(),
+        vec![(Year::new(2022), Month::December, transfer!(+01:00))]
+    );
+    assert_eq!(ledger.balance(), transfer!(+01:00));
+    assert_eq!(ledger.problems(), vec![]);
+}
+
+#[test]
+fn test_filled_transfer_takes_previous_month() {
+    let months = vec![
+        make_month(10, Some(transfer!(+02:00)), &[4, 5, 6], 0),
+        // 10:00 in november, so the 2:00 from october are carried on
+        make_month(11, None, &[2, 3], 2),
+    ];
+
+    let ledger = OvertimeLedger::from_months(&make_global(), "MENSA", months).unwrap();
+
+    assert_eq!(
+        ledger.transfer(Year::new(2022), Month::November),
+        Some(Transfer::new(
+            working_duration!(02:00),
+            working_duration!(02:00)
+        ))
+    );
+    assert_eq!(ledger.balance(), transfer!(+02:00));
+    assert_eq!(ledger.problems(), vec![]);
+}
+
+#[test]
+fn test_mismatched_transfers() {
+    let months = vec![
+        make_month(10, Some(transfer!(+02:00)), &[4, 5, 6], 0),
+        // only 1:00 of the 2:00 from october is taken into november
+        make_month(11, Some(transfer!(-01:00)), &[2, 3], 0),
+    ];
+
+    let ledger = OvertimeLedger::from_months(&make_global(), "MENSA", months).unwrap();
+
+    assert_eq!(ledger.balance(), transfer!(+00:00));
+    assert_eq!(
+        ledger.problems(),
+        vec![
+            OvertimeProblem::Mismatch {
+                year: Year::new(2022),
+                month: Month::October,
+                next: working_duration!(02:00),
+                previous: working_duration!(01:00),
+            },
+            OvertimeProblem::Balance {
+                year: Year::new(2022),
+                month: Month::November,
+                stated: working_duration!(00:00),
+                actual: transfer!(-01:00),
+            },
+        ]
+    );
+}
@@ -0,0 +1,111 @@
+//! Tests that the time sheet is only rendered again, if the input changed.
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::Config;
+use time_sheet::renderer::SheetRenderer;
+use time_sheet::{generate_time_sheet, regenerate_time_sheet, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_config(output: &Path, end: &str) -> Config {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(04:00)))
+        .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = 11\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "[entries.2]\n",
+            "action = \"Tutorium\"\n",
+            "start = \"10:00\"\n",
+            "end = \"{}\"\n",
+        ),
+        end
+    ))
+    .expect("toml should be valid");
+
+    let mut builder = Config::try_from_toml(month, global).expect("config should be valid");
+    builder.output(output.join("sheet.txt"));
+    builder.build().expect("config should build")
+}
+
+/// A slow renderer, that counts how often it has been used.
+#[derive(Default)]
+struct CountingRenderer {
+    renders: AtomicUsize,
+}
+
+impl CountingRenderer {
+    fn renders(&self) -> usize {
+        self.renders.load(Ordering::SeqCst)
+    }
+}
+
+impl SheetRenderer for CountingRenderer {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn extension(&self) -> &str {
+        "txt"
+    }
+
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>> {
+        self.renders.fetch_add(1, Ordering::SeqCst);
+        Ok(config.month().total_working_time().to_string().into_bytes())
+    }
+
+    fn is_cacheable(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_unchanged_month_is_not_rendered() {
+    let output = tempfile::tempdir().unwrap();
+    let renderer = CountingRenderer::default();
+
+    let config = make_config(output.path(), "12:00");
+    generate_time_sheet(&config, &renderer).unwrap();
+    generate_time_sheet(&make_config(output.path(), "12:00"), &renderer).unwrap();
+
+    assert_eq!(renderer.renders(), 1);
+    // the digest is stored together with its algorithm
+    assert!(fs::read_to_string(output.path().join(".sheet.txt.hash"))
+        .unwrap()
+        .starts_with("fnv1a-64:"));
+
+    // a forced render ignores the cache
+    regenerate_time_sheet(&config, &renderer).unwrap();
+    assert_eq!(renderer.renders(), 2);
+}
+
+#[test]
+fn test_changed_month_is_rendered() {
+    let output = tempfile::tempdir().unwrap();
+    let renderer = CountingRenderer::default();
+
+    generate_time_sheet(&make_config(output.path(), "12:00"), &renderer).unwrap();
+    generate_time_sheet(&make_config(output.path(), "13:00"), &renderer).unwrap();
+
+    assert_eq!(renderer.renders(), 2);
+    assert_eq!(
+        fs::read_to_string(output.path().join("sheet.txt")).unwrap(),
+        "03:00"
+    );
+
+    // a deleted output is rendered again
+    fs::remove_file(output.path().join("sheet.txt")).unwrap();
+    generate_time_sheet(&make_config(output.path(), "13:00"), &renderer).unwrap();
+    assert_eq!(renderer.renders(), 3);
+}
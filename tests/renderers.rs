@@ -0,0 +1,124 @@
+//! Tests that the time sheet can be rendered through the different backends.
+
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::Config;
+use time_sheet::renderer::{Renderers, SheetRenderer};
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_config() -> Config {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(04:00)))
+        .expect("toml should be valid");
+
+    let month: toml_input::Month = toml::from_str(concat!(
+        //
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "\n",
+        "[entries.2]\n",
+        "action = \"meeting, weekly\"\n",
+        "start = \"10:00\"\n",
+        "end = \"12:00\"\n",
+        "\n",
+        "[entries.4]\n",
+        "action = \"task a\"\n",
+        "start = \"08:00\"\n",
+        "end = \"10:00\"\n",
+    ))
+    .expect("toml should be valid");
+
+    Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build")
+}
+
+/// A renderer that only counts the entries.
+struct CountingRenderer;
+
+impl SheetRenderer for CountingRenderer {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn extension(&self) -> &str {
+        "txt"
+    }
+
+    fn render(&self, config: &Config) -> anyhow::Result<Vec<u8>> {
+        let month = config.month().to_month_file();
+        Ok(month.entries().len().to_string().into_bytes())
+    }
+}
+
+#[test]
+fn test_default_renderers() {
+    assert_eq!(
+        Renderers::default().names().collect::<Vec<_>>(),
+        vec!["latex-pdf", "latex", "json", "csv", "table"]
+    );
+}
+
+#[test]
+fn test_custom_renderer() {
+    let mut renderers = Renderers::default();
+    renderers.register(CountingRenderer);
+
+    let renderer = renderers
+        .get("count")
+        .expect("renderer should be registered");
+
+    assert_eq!(renderer.render(&make_config()).unwrap(), b"2".to_vec());
+}
+
+#[test]
+fn test_csv_renderer() {
+    let renderers = Renderers::default();
+    let renderer = renderers.get("csv").expect("csv should be registered");
+
+    assert_eq!(
+        String::from_utf8(renderer.render(&make_config()).unwrap()).unwrap(),
+        concat!(
+            "date,action,start,end,pause,duration,vacation,sick\n",
+            "2022-11-02,\"meeting, weekly\",10:00,12:00,00:00,02:00,false,false\n",
+            "2022-11-04,task a,08:00,10:00,00:00,02:00,false,false\n",
+        )
+    );
+}
+
+#[test]
+fn test_json_renderer() {
+    let config = make_config();
+    let renderers = Renderers::default();
+    let renderer = renderers.get("json").expect("json should be registered");
+
+    assert_eq!(
+        String::from_utf8(renderer.render(&config).unwrap()).unwrap(),
+        config.to_month_json().unwrap()
+    );
+}
+
+#[test]
+fn test_table_renderer() {
+    let renderers = Renderers::default();
+    let renderer = renderers.get("table").expect("table should be registered");
+
+    assert_eq!(
+        String::from_utf8(renderer.render(&make_config()).unwrap()).unwrap(),
+        concat!(
+            "Day  Weekday    Start  End    Pause  Duration  Source     Action\n",
+            "  2  Wednesday  10:00  12:00  00:00     02:00  fixed      meeting, weekly\n",
+            "  4  Friday     08:00  10:00  00:00     02:00  fixed      task a\n",
+            "                            week 1:     04:00\n",
+            "\n",
+            "                             total:     04:00\n",
+            "                          expected:     04:00\n",
+        )
+    );
+}
@@ -103,3 +103,48 @@ fn test_repeating_and_absence() {
         )
     );
 }
+
+#[test]
+fn test_end_before_start() {
+    let month_with = |table: &str| {
+        toml::from_str::<toml_input::Month>(&format!(
+            concat!(
+                "[general]\n",
+                "month = 11\n",
+                "year = 2022\n",
+                "department = \"MENSA\"\n",
+                "\n",
+                "{}",
+                "start = \"12:00\"\n",
+                "end = \"08:00\"\n",
+            ),
+            table
+        ))
+    };
+
+    assert!(month_with("[entries.08]\naction = \"work\"\n")
+        .unwrap_err()
+        .to_string()
+        .contains("the entry `work` on day 8 ends at 08:00, which is before its start at 12:00"));
+    assert!(month_with("[absence.08]\n")
+        .unwrap_err()
+        .to_string()
+        .contains("the absence on day 8 ends at 08:00, which is before its start at 12:00"));
+    // an absence over multiple days can end earlier on the last day:
+    assert!(month_with("[absence.\"08-09\"]\n").is_ok());
+
+    let error = toml::from_str::<Global>(
+        &(common::make_global(working_duration!(40:00))
+            + concat!(
+                "[repeating.\"regular work\"]\n",
+                "start = \"12:00\"\n",
+                "end = \"08:00\"\n",
+                "repeats_on = [\"Tuesday\"]\n",
+                "repeats_every = \"week\"\n"
+            )),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains(
+        "the repeating event `regular work` ends at 08:00, which is before its start at 12:00"
+    ));
+}
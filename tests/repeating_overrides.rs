@@ -0,0 +1,166 @@
+//! Tests that single occurrences of repeating events can be cancelled
+//! or changed.
+
+use time_sheet::input::json_input::{Entry, MonthFile};
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::Config;
+use time_sheet::time::{Date, Month, Year};
+use time_sheet::{date, time_stamp, transfer, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"Tutorium\"]\n",
+            "start = \"14:00\"\n",
+            "end = \"16:00\"\n",
+            "repeats_on = [\"Tuesday\"]\n",
+            "repeats_every = \"week\"\n",
+            "except = [\"2022-11-08\"]\n",
+            "\n",
+            "[repeating.\"Tutorium\".overrides.\"2022-11-15\"]\n",
+            "start = \"16:00\"\n",
+            "end = \"18:00\"\n",
+            "\n",
+            "[repeating.\"Tutorium\".overrides.\"2022-11-22\"]\n",
+            "action = \"Tutorium (Vertretung)\"\n",
+            "\n",
+            "[repeating.\"Tutorium\".overrides.\"2022-11-16\"]\n",
+            "start = \"10:00\"\n",
+            "end = \"12:00\"\n",
+        ),
+        common::make_global(working_duration!(08:00))
+    ))
+    .expect("toml should be valid")
+}
+
+#[test]
+fn test_except_and_overrides() {
+    let month: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+    ))
+    .expect("toml should be valid");
+
+    assert_eq!(
+        common::make_month_file(make_global(), month),
+        MonthFile::new(
+            Year::new(2022),
+            Month::November,
+            transfer!(+00:00),
+            // the 1st is a holiday and the 8th is excluded
+            vec![
+                Entry::new("Tutorium", 15, time_stamp!(16:00), time_stamp!(18:00), None),
+                Entry::new(
+                    "Tutorium (Vertretung)",
+                    22,
+                    time_stamp!(14:00),
+                    time_stamp!(16:00),
+                    None
+                ),
+                Entry::new("Tutorium", 29, time_stamp!(14:00), time_stamp!(16:00), None),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_unmatched_overrides() {
+    let global = make_global();
+
+    assert_eq!(
+        global
+            .unmatched_overrides(Year::new(2022), Month::November, |_| true, "MENSA")
+            .collect::<Vec<_>>(),
+        vec![("Tutorium", date!(2022:11:16))]
+    );
+
+    // an override on a day where the event can not repeat is not matched either
+    assert_eq!(
+        global
+            .unmatched_overrides(
+                Year::new(2022),
+                Month::November,
+                |date: Date| date.day() != 15,
+                "MENSA"
+            )
+            .collect::<Vec<_>>(),
+        vec![
+            ("Tutorium", date!(2022:11:15)),
+            ("Tutorium", date!(2022:11:16))
+        ]
+    );
+
+    assert_eq!(
+        global
+            .unmatched_overrides(Year::new(2022), Month::December, |_| true, "MENSA")
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_config_reports_unmatched_overrides() {
+    let month: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+    ))
+    .expect("toml should be valid");
+
+    let config = Config::try_from_toml(month, make_global())
+        .expect("config should be valid")
+        .build()
+        .expect("config should build");
+
+    assert_eq!(
+        config.unmatched_overrides(),
+        &[("Tutorium".to_string(), date!(2022:11:16))]
+    );
+}
+
+#[test]
+fn test_invalid_overrides() {
+    let global_with = |event: &str| {
+        toml::from_str::<Global>(&format!(
+            "{}{}",
+            common::make_global(working_duration!(08:00)),
+            event
+        ))
+        .unwrap_err()
+        .to_string()
+    };
+
+    // the override ends before the start of the event:
+    assert!(global_with(concat!(
+        "[repeating.\"Tutorium\"]\n",
+        "start = \"14:00\"\n",
+        "end = \"16:00\"\n",
+        "repeats_on = [\"Tuesday\"]\n",
+        "repeats_every = \"week\"\n",
+        "\n",
+        "[repeating.\"Tutorium\".overrides.\"2022-11-15\"]\n",
+        "end = \"12:00\"\n",
+    ))
+    .contains("the override of `Tutorium` on 2022-11-15 ends at 12:00, which is not after its start at 14:00"));
+
+    // dynamic events have no time that could be overridden:
+    assert!(global_with(concat!(
+        "[repeating.\"Tutorium\"]\n",
+        "duration = \"02:00\"\n",
+        "repeats_on = [\"Tuesday\"]\n",
+        "repeats_every = \"week\"\n",
+        "\n",
+        "[repeating.\"Tutorium\".overrides.\"2022-11-15\"]\n",
+        "action = \"Tutorium (Vertretung)\"\n",
+    ))
+    .contains("the repeating event `Tutorium` is dynamic and can not be overridden on 2022-11-15"));
+}
@@ -0,0 +1,148 @@
+//! Tests that repeating events with an `rrule` are expanded into the
+//! correct entries of the month.
+
+use time_sheet::input::json_input::{Entry, MonthFile};
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::time::{Month, Year};
+use time_sheet::{time_stamp, transfer, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_month() -> toml_input::Month {
+    toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+    ))
+    .expect("toml should be valid")
+}
+
+#[test]
+fn test_rrule_events() {
+    let global: Global = toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"Tutorium\"]\n",
+            "start = \"14:00\"\n",
+            "end = \"16:00\"\n",
+            "rrule = \"FREQ=WEEKLY;INTERVAL=2;BYDAY=TH\"\n",
+            "start_date = \"2022-10-20\"\n",
+            "\n",
+            "[repeating.\"Besprechung\"]\n",
+            "start = \"10:00\"\n",
+            "end = \"11:00\"\n",
+            "rrule = \"FREQ=MONTHLY;BYDAY=1MO\"\n",
+            "start_date = \"2022-01-01\"\n",
+            "\n",
+            "[repeating.\"Abrechnung\"]\n",
+            "start = \"09:00\"\n",
+            "end = \"10:00\"\n",
+            "rrule = \"FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1\"\n",
+            "start_date = \"2022-01-01\"\n",
+        ),
+        common::make_global(working_duration!(10:00))
+    ))
+    .expect("toml should be valid");
+
+    assert_eq!(
+        common::make_month_file(global, make_month()),
+        MonthFile::new(
+            Year::new(2022),
+            Month::November,
+            transfer!(+00:00),
+            vec![
+                Entry::new("Tutorium", 3, time_stamp!(14:00), time_stamp!(16:00), None),
+                Entry::new(
+                    "Besprechung",
+                    7,
+                    time_stamp!(10:00),
+                    time_stamp!(11:00),
+                    None
+                ),
+                Entry::new("Tutorium", 17, time_stamp!(14:00), time_stamp!(16:00), None),
+                Entry::new(
+                    "Abrechnung",
+                    30,
+                    time_stamp!(09:00),
+                    time_stamp!(10:00),
+                    None
+                ),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_rrule_count() {
+    // the fourth occurrence would be on the 17th
+    let global: Global = toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"Tutorium\"]\n",
+            "start = \"14:00\"\n",
+            "end = \"16:00\"\n",
+            "rrule = \"FREQ=WEEKLY;BYDAY=TH;COUNT=3\"\n",
+            "start_date = \"2022-10-27\"\n",
+        ),
+        common::make_global(working_duration!(04:00))
+    ))
+    .expect("toml should be valid");
+
+    assert_eq!(
+        common::make_month_file(global, make_month()),
+        MonthFile::new(
+            Year::new(2022),
+            Month::November,
+            transfer!(+00:00),
+            vec![
+                Entry::new("Tutorium", 3, time_stamp!(14:00), time_stamp!(16:00), None),
+                Entry::new("Tutorium", 10, time_stamp!(14:00), time_stamp!(16:00), None),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_repeats_every_with_rrule() {
+    let error = toml::from_str::<Global>(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"Tutorium\"]\n",
+            "start = \"14:00\"\n",
+            "end = \"16:00\"\n",
+            "rrule = \"FREQ=WEEKLY;BYDAY=TH\"\n",
+            "start_date = \"2022-10-27\"\n",
+            "repeats_every = \"2 weeks\"\n",
+        ),
+        common::make_global(working_duration!(04:00))
+    ))
+    .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("can not have both `rrule` and `repeats_every`"));
+}
+
+#[test]
+fn test_missing_repeats_every() {
+    // without an rrule, the interval must be set
+    let error = toml::from_str::<Global>(&format!(
+        concat!(
+            "{}",
+            "[repeating.\"Tutorium\"]\n",
+            "start = \"14:00\"\n",
+            "end = \"16:00\"\n",
+            "repeats_on = [\"Thursday\"]\n",
+        ),
+        common::make_global(working_duration!(04:00))
+    ))
+    .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("the repeating event `Tutorium` is missing `repeats_every`"));
+}
@@ -0,0 +1,115 @@
+//! Tests that the scheduler can be configured through the contract in the
+//! global file and the month file.
+
+use std::collections::HashMap;
+
+use time_sheet::input::json_input::MonthFile;
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::time::{Date, WorkingDuration};
+use time_sheet::{time_stamp, working_duration};
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(
+        &(common::make_global(working_duration!(10:00))
+            + concat!(
+                "[contract.MENSA.scheduler]\n",
+                "daily_limit = \"01:00\"\n",
+                "weekly_limit = \"02:00\"\n",
+            )),
+    )
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn make_month(scheduler: &str) -> toml_input::Month {
+    toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = 11\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "{}",
+            "\n",
+            "[dynamic.\"filler\"]\n",
+            "duration = \"10:00\"\n",
+        ),
+        scheduler
+    ))
+    .expect("toml should be valid")
+}
+
+#[must_use]
+fn weekly_durations(month_file: &MonthFile) -> Vec<WorkingDuration> {
+    let mut weeks: HashMap<usize, WorkingDuration> = HashMap::new();
+
+    for entry in month_file.entries() {
+        let date = Date::new(month_file.year(), month_file.month(), entry.day()).unwrap();
+        *weeks.entry(date.week_number()).or_default() += entry.work_duration();
+    }
+
+    let mut result = weeks.into_iter().collect::<Vec<_>>();
+    result.sort();
+    result.into_iter().map(|(_, duration)| duration).collect()
+}
+
+#[test]
+fn test_contract_scheduler_settings() {
+    let month_file = common::make_month_file(make_global(), make_month(""));
+
+    assert!(weekly_durations(&month_file)
+        .into_iter()
+        .all(|duration| duration <= working_duration!(02:00)));
+    assert!(month_file
+        .entries()
+        .iter()
+        .all(|entry| entry.work_duration() <= working_duration!(01:00)));
+}
+
+#[test]
+fn test_month_overrides_scheduler_settings() {
+    let month_file = common::make_month_file(
+        make_global(),
+        make_month("[scheduler]\nweekly_limit = \"03:00\"\n"),
+    );
+
+    assert!(weekly_durations(&month_file)
+        .into_iter()
+        .any(|duration| duration > working_duration!(02:00)));
+    assert!(month_file
+        .entries()
+        .iter()
+        .all(|entry| entry.work_duration() <= working_duration!(01:00)));
+}
+
+#[test]
+fn test_deprecated_general_working_hours() {
+    // the working hours used to be specified in the general section
+    let month_file = common::make_month_file(
+        make_global(),
+        make_month("working_hours = \"14:00-16:00\"\n"),
+    );
+
+    assert!(!month_file.entries().is_empty());
+    assert!(month_file.entries().iter().all(|entry| {
+        entry.time_span().start() >= time_stamp!(14:00)
+            && entry.time_span().end() <= time_stamp!(16:00)
+    }));
+}
+
+#[test]
+fn test_weekly_limit_below_daily_limit() {
+    // a weekly limit below the daily limit is valid, it is just the stricter one
+    let month_file = common::make_month_file(
+        make_global(),
+        make_month("[scheduler]\nweekly_limit = \"00:30\"\n"),
+    );
+
+    assert!(!month_file.entries().is_empty());
+    assert!(weekly_durations(&month_file)
+        .into_iter()
+        .all(|duration| duration <= working_duration!(00:30)));
+}
@@ -0,0 +1,148 @@
+//! Tests that sick days are credited towards the working time of the month
+//! and that nothing else is scheduled on them.
+
+use time_sheet::input::json_input::Entry;
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::Config;
+use time_sheet::renderer::Renderers;
+use time_sheet::time::WorkingDuration;
+use time_sheet::{time_stamp, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_month(rest: &str) -> toml_input::Month {
+    toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = 11\n",
+            "year = 2022\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "[dynamic.\"filler\"]\n",
+            "duration = \"20:00\"\n",
+            "\n",
+            "{}",
+        ),
+        rest
+    ))
+    .expect("toml should be valid")
+}
+
+#[test]
+fn test_sick_leave_uses_availability() {
+    let global: Global = toml::from_str(&format!(
+        concat!(
+            "{}",
+            "[contract.MENSA.scheduler.availability]\n",
+            "tuesday = [\"14:00-18:00\"]\n",
+            "thursday = [\"08:00-12:00\"]\n",
+        ),
+        common::make_global(working_duration!(20:00))
+    ))
+    .expect("toml should be valid");
+
+    let month_file = common::make_month_file(global, make_month("[sick.14-18]\n"));
+
+    let in_range = month_file
+        .entries()
+        .iter()
+        .filter(|entry| (14..=18).contains(&entry.day()))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        in_range,
+        vec![
+            Entry::new_sick("Krankheit", 15, time_stamp!(14:00), time_stamp!(18:00)),
+            Entry::new_sick("Krankheit", 17, time_stamp!(08:00), time_stamp!(12:00)),
+        ]
+    );
+
+    // the sick days count towards the working time of the month
+    assert_eq!(
+        month_file
+            .entries()
+            .iter()
+            .map(Entry::work_duration)
+            .sum::<WorkingDuration>(),
+        working_duration!(20:00)
+    );
+}
+
+#[test]
+fn test_sick_leave_is_reported_separately() {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(40:00)))
+        .expect("toml should be valid");
+    let month = make_month(concat!(
+        "[sick.2]\n",
+        "action = \"Arzt\"\n",
+        "start = \"08:00\"\n",
+        "\n",
+        "[entries.3]\n",
+        "action = \"Krankheit\"\n",
+        "start = \"09:00\"\n",
+        "end = \"11:00\"\n",
+        "is_sick = true\n",
+    ));
+
+    let config = Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build");
+
+    let month_file = config.month().to_month_file();
+    let sick = month_file
+        .entries()
+        .iter()
+        .filter(|entry| entry.is_sick())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // without an availability a sick day is as long as a day of vacation
+    assert_eq!(
+        sick,
+        vec![
+            Entry::new_sick("Arzt", 2, time_stamp!(08:00), time_stamp!(09:52)),
+            Entry::new_sick("Krankheit", 3, time_stamp!(09:00), time_stamp!(11:00)),
+        ]
+    );
+
+    let renderers = Renderers::default();
+    let table = renderers
+        .get("table")
+        .expect("table should be registered")
+        .render(&config)
+        .unwrap();
+
+    assert!(String::from_utf8(table)
+        .unwrap()
+        .lines()
+        .any(|line| line.split_whitespace().eq(["sick:", "03:52"])));
+}
+
+#[test]
+fn test_sick_leave_for_jar() {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(40:00)))
+        .expect("toml should be valid");
+    let month = make_month("[sick.2]\n");
+
+    let config = Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build");
+
+    // the month.json of the json renderer marks the sick days
+    assert!(config.to_month_json().unwrap().contains("\"sick\": true"));
+
+    // but the jar does not know the field
+    let month_file = config.month().to_month_file().for_jar();
+    assert!(month_file.entries().iter().all(|entry| !entry.is_sick()));
+    assert!(month_file
+        .entries()
+        .iter()
+        .any(|entry| entry.day() == 2 && entry.action() == "Krankheit"));
+    assert!(!serde_json::to_string(&month_file).unwrap().contains("sick"));
+}
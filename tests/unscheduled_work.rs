@@ -0,0 +1,124 @@
+//! Tests that dynamic work, which does not fit into the month, is reported
+//! and handled like configured.
+
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::{Config, Transfer, UnscheduledWork};
+use time_sheet::time::WorkingDuration;
+use time_sheet::{transfer, working_duration};
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+fn make_input(policy: Option<&str>) -> (Global, toml_input::Month) {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(10:00)))
+        .expect("toml should be valid");
+
+    let mut month = concat!(
+        //
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+    )
+    .to_string();
+
+    if let Some(policy) = policy {
+        month += &format!("unscheduled = \"{}\"\n", policy);
+    }
+
+    month += concat!(
+        "\n",
+        "[dynamic.\"task a\"]\n",
+        "duration = \"06:00\"\n",
+        "\n",
+        "[dynamic.\"task b\"]\n",
+        "duration = \"07:00\"\n",
+    );
+
+    (
+        global,
+        toml::from_str(&month).expect("toml should be valid"),
+    )
+}
+
+#[test]
+fn test_unscheduled_work_is_reported() {
+    let (global, month) = make_input(None);
+
+    let config = Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build");
+
+    assert_eq!(
+        config.month().unscheduled_work(),
+        vec![UnscheduledWork::new("task b", working_duration!(03:00))]
+    );
+    assert_eq!(config.month().scheduled_transfer(), transfer!(+00:00));
+}
+
+#[test]
+fn test_unscheduled_work_error() {
+    let (global, month) = make_input(Some("error"));
+
+    let error = Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect_err("unscheduled work should be an error");
+
+    assert_eq!(
+        error.to_string(),
+        "failed to schedule the dynamic work of `task b` (03:00)".to_string()
+    );
+}
+
+#[test]
+fn test_unscheduled_work_transfer() {
+    let global: Global = toml::from_str(&common::make_global(working_duration!(10:00)))
+        .expect("toml should be valid");
+    // only one hour per week can be scheduled, so the month falls short
+    let month: toml_input::Month = toml::from_str(concat!(
+        "[general]\n",
+        "month = 11\n",
+        "year = 2022\n",
+        "department = \"MENSA\"\n",
+        "unscheduled = \"transfer\"\n",
+        "\n",
+        "[scheduler]\n",
+        "weekly_limit = \"01:00\"\n",
+        "\n",
+        "[dynamic.\"task a\"]\n",
+        "duration = \"10:00\"\n",
+    ))
+    .expect("toml should be valid");
+
+    let config = Config::try_from_toml(month, global)
+        .expect("config should be valid")
+        .build()
+        .expect("config should build");
+
+    let month_file = config.month().to_month_file();
+    let worked = month_file
+        .entries()
+        .iter()
+        .map(|entry| entry.work_duration())
+        .sum::<WorkingDuration>();
+
+    assert_eq!(
+        config.month().unscheduled_work(),
+        vec![UnscheduledWork::new(
+            "task a",
+            working_duration!(10:00) - worked
+        )]
+    );
+    assert_eq!(
+        month_file.transfer(),
+        Transfer::negative(working_duration!(10:00) - worked)
+    );
+    // worked + previous - next = expected
+    assert_eq!(
+        worked + month_file.transfer().previous() - month_file.transfer().next(),
+        working_duration!(10:00)
+    );
+}
@@ -0,0 +1,105 @@
+//! Tests that the vacation of a year is summed up and compared to the
+//! entitlement of the contract.
+
+use time_sheet::input::toml_input::{self, Global};
+use time_sheet::input::{VacationLedger, VacationProblem};
+use time_sheet::time::Year;
+use time_sheet::working_duration;
+
+use pretty_assertions::assert_eq;
+
+mod common;
+
+#[must_use]
+fn make_global() -> Global {
+    toml::from_str(&common::make_global(working_duration!(40:00))).expect("toml should be valid")
+}
+
+#[must_use]
+fn make_month(month: usize, holiday_months: Option<usize>) -> toml_input::Month {
+    let holiday = holiday_months
+        .map(|months| format!("[holiday]\nday = 10\nmonths = {}\n", months))
+        .unwrap_or_default();
+
+    toml::from_str(&format!(
+        concat!(
+            "[general]\n",
+            "month = {}\n",
+            "year = 2023\n",
+            "department = \"MENSA\"\n",
+            "\n",
+            "{}",
+        ),
+        month, holiday
+    ))
+    .expect("toml should be valid")
+}
+
+#[test]
+fn test_complete_year() {
+    let months = (1..=12).map(|month| make_month(month, Some(1)));
+    let ledger =
+        VacationLedger::from_months(&make_global(), "MENSA", Year::new(2023), months).unwrap();
+
+    assert_eq!(ledger.contract_months(), 12);
+    assert_eq!(ledger.holiday_months(), 12);
+    assert_eq!(ledger.entitlement(), working_duration!(37:11));
+    assert_eq!(ledger.taken(), working_duration!(37:12));
+    assert_eq!(ledger.problems(), vec![]);
+}
+
+#[test]
+fn test_remaining_vacation() {
+    let months = (1..=6).map(|month| make_month(month, (month == 3).then_some(2)));
+    let ledger =
+        VacationLedger::from_months(&make_global(), "MENSA", Year::new(2023), months).unwrap();
+
+    assert_eq!(ledger.taken(), working_duration!(06:12));
+    assert_eq!(ledger.remaining(), working_duration!(30:59));
+    // the year is not over yet
+    assert_eq!(ledger.problems(), vec![]);
+}
+
+#[test]
+fn test_missing_holidays() {
+    let months = (1..=12).map(|month| make_month(month, (month % 2 == 0).then_some(1)));
+    let ledger =
+        VacationLedger::from_months(&make_global(), "MENSA", Year::new(2023), months).unwrap();
+
+    assert_eq!(
+        ledger.problems(),
+        vec![VacationProblem::HolidayMonths {
+            taken: 6,
+            expected: 12
+        }]
+    );
+}
+
+#[test]
+fn test_over_taken() {
+    let months = (1..=12).map(|month| make_month(month, (month == 1).then_some(12)));
+    let ledger = VacationLedger::from_months(&make_global(), "MENSA", Year::new(2023), months)
+        .unwrap()
+        .with_carry_over(working_duration!(01:00));
+
+    assert_eq!(ledger.available(), working_duration!(38:11));
+    assert_eq!(ledger.problems(), vec![]);
+
+    let months = (1..=2).map(|month| make_month(month, Some(12)));
+    let ledger =
+        VacationLedger::from_months(&make_global(), "MENSA", Year::new(2023), months).unwrap();
+
+    assert_eq!(
+        ledger.problems(),
+        vec![
+            VacationProblem::OverTaken {
+                taken: working_duration!(74:22),
+                available: working_duration!(37:11),
+            },
+            VacationProblem::HolidayMonths {
+                taken: 24,
+                expected: 12
+            }
+        ]
+    );
+}